@@ -11,4 +11,15 @@ fn criterion_benchmark(c: &mut Criterion) {
 
         criterion::black_box(board.copy_make_move(m));
     }));
+
+    c.bench_function("make_unmake_move", |b| {
+        let mut board = Board::default();
+        let m = Move::new(Square::E2, Square::E4, None);
+
+        b.iter(|| {
+            let restorer = board.make_move(m);
+            criterion::black_box(&board);
+            board.unmake_move(m, restorer);
+        });
+    });
 }
@@ -12,6 +12,22 @@ fn criterion_benchmark(c: &mut Criterion) {
             assert_eq!(perft::<true>(&board, i + 1), expected, "D{}", i + 1);
         }
     }));
+
+    c.bench_function("perft_unmake", |b| b.iter(|| {
+        let mut board = Board::default();
+
+        for (i, expected) in [20, 400, 8_902, 197_281, 4_865_609].into_iter().enumerate() {
+            assert_eq!(perft_unmake(&mut board, i + 1), expected, "D{}", i + 1);
+        }
+    }));
+
+    c.bench_function("perft_legal", |b| b.iter(|| {
+        let board = Board::default();
+
+        for (i, expected) in [20, 400, 8_902, 197_281, 4_865_609].into_iter().enumerate() {
+            assert_eq!(perft_legal(&board, i + 1), expected, "D{}", i + 1);
+        }
+    }));
 }
 
 fn perft<const ROOT: bool>(board: &Board, depth: usize) -> u64 {
@@ -30,3 +46,35 @@ fn perft<const ROOT: bool>(board: &Board, depth: usize) -> u64 {
 
     total
 }
+
+fn perft_unmake(board: &mut Board, depth: usize) -> u64 {
+    if depth == 0 { return 1 };
+
+    let moves = board.pseudo_legal_moves(&[]).collect::<MoveList>();
+    let mut total = 0;
+    for &m in &moves {
+        let restorer = board.make_move(m);
+
+        if !board.is_illegal() {
+            total += perft_unmake(board, depth - 1);
+        }
+
+        board.unmake_move(m, restorer);
+    }
+
+    total
+}
+
+fn perft_legal(board: &Board, depth: usize) -> u64 {
+    if depth == 1 { return board.legal_moves(&[]).count() as u64 };
+
+    let mut total = 0;
+    for m in board.legal_moves(&[]) {
+        let mut this = *board;
+        this.make_move(m);
+
+        total += perft_legal(&this, depth - 1);
+    }
+
+    total
+}
@@ -75,8 +75,55 @@ impl Bitboard {
         self.0.count_ones()
     }
 
+    /// Get the squares strictly between two squares if they are on the same rank, file or
+    /// diagonal, otherwise an empty bitboard.
+    ///
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// assert_eq!(
+    ///     Bitboard::between(Square::A1, Square::D4),
+    ///     Bitboard::from(Square::B2) | Square::C3.into(),
+    /// );
+    /// assert!(Bitboard::between(Square::A1, Square::B3).is_empty());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn between(a: Square, b: Square) -> Self {
+        // SAFETY: squares are < 64
+        unsafe { *crate::line::BETWEEN.get_unchecked(a.to_usize()).get_unchecked(b.to_usize()) }
+    }
+
+    /// Get the full line across the board that passes through two squares if they are on the same
+    /// rank, file or diagonal, otherwise an empty bitboard.
+    ///
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// assert_eq!(Bitboard::line(Square::C3, Square::E5), Bitboard::line(Square::A1, Square::H8));
+    /// assert_eq!(Bitboard::line(Square::A2, Square::C2), Rank::_2.into());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn line(a: Square, b: Square) -> Self {
+        // SAFETY: squares are < 64
+        unsafe { *crate::line::LINE.get_unchecked(a.to_usize()).get_unchecked(b.to_usize()) }
+    }
+
     /// The 4 edges of the board combined.
     pub const EDGE: Self = crate::bb_data::EDGE;
+
+    /// All light squares, e.g. `h1`.
+    ///
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// assert!(!(Bitboard::LIGHT_SQUARES & Square::H1.into()).is_empty());
+    /// assert_eq!(Bitboard::LIGHT_SQUARES.popcnt(), 32);
+    /// ```
+    pub const LIGHT_SQUARES: Self = Self(0x55aa55aa55aa55aa);
+    /// All dark squares, e.g. `a1`.
+    pub const DARK_SQUARES: Self = Self(!Self::LIGHT_SQUARES.0);
 }
 
 impl Deref for Bitboard {
@@ -0,0 +1,99 @@
+//! Cuckoo tables of the hash differences of reversible piece moves, used to detect that a
+//! repetition can be reached with a single move. See Marcel van Kervinck's paper "The design of
+//! Rookie 2.0" and [`crate::game::Game::has_upcoming_repetition`].
+
+use crate::{chess_move::Move, square::Square};
+
+use super::zobrist;
+
+const SIZE: usize = 8192;
+
+/// The number of reversible moves of knights, bishops, rooks, queens and kings on an empty board
+/// for both colors, counting each pair of squares once.
+const COUNT: usize = 3668;
+
+/// The keys and moves of the table, built from the polyglot piece keys at compile time.
+static TABLES: ([u64; SIZE], [Option<Move>; SIZE]) = {
+    let mut keys = [0; SIZE];
+    let mut moves = [None; SIZE];
+    let mut count = 0;
+
+    let mut kind = 2;
+    while kind < 12 {
+        // polyglot pieces alternate between black and white
+        let piece = kind * 64;
+
+        let mut from = 0;
+        while from < 64 {
+            let mut to = from + 1;
+            while to < 64 {
+                if can_move(kind / 2, from, to) {
+                    let mut key = zobrist::PIECES[piece + from as usize]
+                        ^ zobrist::PIECES[piece + to as usize]
+                        ^ zobrist::SIDE_TO_MOVE;
+                    let mut mov = Some(Move::new(Square::from_index(from), Square::from_index(to), None));
+                    let mut i = h1(key);
+
+                    loop {
+                        let (k, m) = (keys[i], moves[i]);
+                        keys[i] = key;
+                        moves[i] = mov;
+                        key = k;
+                        mov = m;
+
+                        if mov.is_none() { break }
+
+                        i = if i == h1(key) { h2(key) } else { h1(key) };
+                    }
+
+                    count += 1;
+                }
+
+                to += 1;
+            }
+
+            from += 1;
+        }
+
+        kind += 1;
+    }
+
+    assert!(count == COUNT);
+    (keys, moves)
+};
+
+#[inline(always)]
+const fn h1(key: u64) -> usize { (key & 0x1fff) as usize }
+
+#[inline(always)]
+const fn h2(key: u64) -> usize { ((key >> 16) & 0x1fff) as usize }
+
+/// Get the move between the two positions whose hashes differ by `key`, if `key` is the hash
+/// difference of a reversible piece move and a change of the side to move.
+#[inline(always)]
+pub fn lookup(key: u64) -> Option<Move> {
+    let (keys, moves) = &TABLES;
+
+    if keys[h1(key)] == key {
+        moves[h1(key)]
+    } else if keys[h2(key)] == key {
+        moves[h2(key)]
+    } else {
+        None
+    }
+}
+
+/// Get if a piece of the given kind (knight, bishop, rook, queen or king) can move between the
+/// two squares on an empty board.
+const fn can_move(piece: usize, from: u8, to: u8) -> bool {
+    let df = (from % 8).abs_diff(to % 8);
+    let dr = (from / 8).abs_diff(to / 8);
+
+    match piece {
+        1 => df * dr == 2,
+        2 => df == dr,
+        3 => df == 0 || dr == 0,
+        4 => df == dr || df == 0 || dr == 0,
+        _ => df <= 1 && dr <= 1,
+    }
+}
@@ -1,4 +1,4 @@
-use core::{str::Chars, fmt};
+use core::{cmp::Ordering, str::Chars, fmt};
 
 use super::{Board, Color, File, Piece, Rank, Square, pawn, zobrist};
 
@@ -8,6 +8,8 @@ pub enum EpdError {
     UnexpectedEnd,
     TooMuchPieces { rank: Rank },
     TooLittleRanks { last_rank: Rank },
+    NoCastleRook(char),
+    InvalidCounter,
 }
 
 impl fmt::Display for EpdError {
@@ -17,6 +19,8 @@ impl fmt::Display for EpdError {
             Self::UnexpectedEnd => write!(f, "unexpected end of EPD string"),
             Self::TooMuchPieces { rank } => write!(f, "too much pieces in rank {rank}"),
             Self::TooLittleRanks { last_rank } => write!(f, "too little ranks, last is rank {last_rank}"),
+            Self::NoCastleRook(ch) => write!(f, "no rook to castle with for castle right `{ch}`"),
+            Self::InvalidCounter => write!(f, "invalid halfmove clock or fullmove number"),
         }
     }
 }
@@ -27,14 +31,17 @@ impl Board {
     /// Parse a EPD string into a board.
     ///
     /// # Note
-    /// This function may not return `Err` on all invalid EPD string.
+    /// This function may not return `Err` on all invalid EPD string. Anything after the en passant
+    /// field is ignored, use [`Self::from_fen`] to parse the halfmove clock and fullmove number.
+    ///
+    /// The castling field can be in the usual `KQkq` form, in Shredder-FEN (`HAha`) where the
+    /// files of the castling rooks are given, or in X-FEN where the two forms are mixed. If
+    /// `chess960` is true, `K` and `Q` in the castling field refers to the outermost rook on that
+    /// side of the king.
     ///
     /// # Errors
     /// This function errors if the EPD string is not valid enough to make the parser parse it.
     ///
-    /// # Panics
-    /// This function panics if `chess960` due to incomplete implementation.
-    ///
     /// # Example
     /// ```
     /// # use dychess::prelude::*;
@@ -45,21 +52,100 @@ impl Board {
     /// ).expect("valid position");
     /// ```
     pub fn from_epd(chess960: bool, epd: &str) -> Result<Self, EpdError> {
-        assert!(!chess960, "chess960 is not supported yet");
+        Self::parse_epd(chess960, &mut epd.chars())
+    }
+
+    /// Parse a FEN string into a board. This is the same as [`Self::from_epd`], except that the
+    /// halfmove clock and fullmove number are parsed as well.
+    ///
+    /// # Note
+    /// The halfmove clock and fullmove number can be omitted, in which case they default to `0`
+    /// and `1` respectively.
+    ///
+    /// # Errors
+    /// This function errors if the FEN string is not valid enough to make the parser parse it.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_fen(
+    ///     false,
+    ///     "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    /// ).expect("valid position");
+    ///
+    /// assert_eq!(board.halfmove_clock(), 0);
+    /// assert_eq!(board.fullmove_number(), 1);
+    /// ```
+    pub fn from_fen(chess960: bool, fen: &str) -> Result<Self, EpdError> {
+        let mut chars = fen.chars();
+        let mut board = Self::parse_epd(chess960, &mut chars)?;
+        let mut counters = chars.as_str().split_ascii_whitespace();
+
+        if let Some(halfmove_clock) = counters.next() {
+            board.halfmove_clock = halfmove_clock.parse().map_err(|_| EpdError::InvalidCounter)?;
+        }
+
+        if let Some(fullmove_number) = counters.next() {
+            let fullmove_number: u16 = fullmove_number.parse().map_err(|_| EpdError::InvalidCounter)?;
+            board.fullmove_number = fullmove_number.max(1);
+        }
+
+        if let Some(extra) = counters.next() {
+            // SAFETY: split by whitespaces so it can't be empty
+            return Err(EpdError::UnexpectedChar(unsafe { extra.chars().next().unwrap_unchecked() }));
+        }
+
+        Ok(board)
+    }
 
+    fn parse_epd(chess960: bool, epd: &mut Chars<'_>) -> Result<Self, EpdError> {
         let mut board = Self::empty();
-        let mut epd = epd.chars();
 
         board.chess960 = chess960;
-        board.parse_epd_header(&mut epd)?;
+        board.parse_epd_header(epd)?;
 
         loop {
             match epd.next() {
-                // TODO: chess960 castling
-                Some('K') => board.allow_king_side_castle(Color::White),
-                Some('Q') => board.allow_queen_side_castle(Color::White),
-                Some('k') => board.allow_king_side_castle(Color::Black),
-                Some('q') => board.allow_queen_side_castle(Color::Black),
+                Some(ch @ ('K' | 'k')) => {
+                    let color = Color::from(ch.is_ascii_lowercase());
+
+                    if chess960 {
+                        let file = board.outermost_rook(color, true).ok_or(EpdError::NoCastleRook(ch))?;
+                        board.castle_rights[color as usize].set_ks_file(file);
+                    }
+
+                    board.allow_king_side_castle(color);
+                },
+                Some(ch @ ('Q' | 'q')) => {
+                    let color = Color::from(ch.is_ascii_lowercase());
+
+                    if chess960 {
+                        let file = board.outermost_rook(color, false).ok_or(EpdError::NoCastleRook(ch))?;
+                        board.castle_rights[color as usize].set_qs_file(file);
+                    }
+
+                    board.allow_queen_side_castle(color);
+                },
+                Some(ch @ ('A'..='H' | 'a'..='h')) => {
+                    let color = Color::from(ch.is_ascii_lowercase());
+                    let file = File::ALL[(ch.to_ascii_lowercase() as u8 - b'a') as usize];
+                    let king = (board.kings() & board.color_combined(color) & color.back_rank().into())
+                        .first_square()
+                        .ok_or(EpdError::NoCastleRook(ch))?;
+
+                    match file.cmp(&king.file()) {
+                        Ordering::Greater => {
+                            board.castle_rights[color as usize].set_ks_file(file);
+                            board.allow_king_side_castle(color);
+                        },
+                        Ordering::Less => {
+                            board.castle_rights[color as usize].set_qs_file(file);
+                            board.allow_queen_side_castle(color);
+                        },
+                        Ordering::Equal => return Err(EpdError::NoCastleRook(ch)),
+                    }
+                },
                 Some(' ') => break,
                 Some('-') => match epd.next() {
                     Some(' ') => break,
@@ -71,10 +157,22 @@ impl Board {
             }
         }
 
-        board.parse_epd_footer(&mut epd)?;
+        board.parse_epd_footer(epd)?;
         Ok(board)
     }
 
+    /// Get the file of the outermost rook on the back rank on the given side of the king.
+    fn outermost_rook(&self, color: Color, king_side: bool) -> Option<File> {
+        let king = (self.kings() & self.color_combined(color) & color.back_rank().into()).first_square()?;
+        let rooks = self.rooks_of(color) & color.back_rank().into();
+
+        if king_side {
+            (rooks & king.file().right_side()).last_square()
+        } else {
+            (rooks & king.file().left_side()).first_square()
+        }.map(Square::file)
+    }
+
     fn parse_epd_header(&mut self, epd: &mut Chars<'_>) -> Result<(), EpdError> {
         for rank in Rank::ALL.into_iter().rev() {
             let mut file = 0;
@@ -137,26 +235,127 @@ impl Board {
             Some(ch) => return Err(EpdError::UnexpectedChar(ch)),
             None => return Err(EpdError::UnexpectedEnd),
         };
+
+        if self.en_passant.is_some() {
+            match epd.next() {
+                Some('3' | '6') => {},
+                Some(ch) => return Err(EpdError::UnexpectedChar(ch)),
+                None => return Err(EpdError::UnexpectedEnd),
+            }
+        }
+
         Ok(())
     }
 }
 
-/// Format `self` into an EPD string.
+/// The notation of the castling field used when formatting a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastlingNotation {
+    /// `KQkq` for the outermost rooks and the rook file otherwise. This is the same as the usual
+    /// notation for normal chess.
+    XFen,
+    /// The rook files for all castling rooks, e.g. `HAha`.
+    Shredder,
+}
+
+/// A helper struct to format a [`Board`] into an EPD or FEN string with the given castling
+/// notation.
 ///
-/// # Example
-/// ```
-/// # use dychess::prelude::*;
-/// #
-/// let initial = Board::default();
-/// assert_eq!(initial.to_string(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -");
-/// ```
-impl fmt::Display for Board {
+/// This is created by [`Board::display_epd`], [`Board::display_fen`] and [`Board::to_fen`].
+#[derive(Debug, Clone, Copy)]
+pub struct EpdDisplay<'a> {
+    board: &'a Board,
+    castling: CastlingNotation,
+    counters: bool,
+}
+
+impl Board {
+    /// Get a helper struct to format `self` into an EPD string with the given castling notation.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::{board::epd::CastlingNotation, prelude::*};
+    /// #
+    /// let board = Board::from_epd(true, "bqnb1rkr/8/8/8/8/8/8/BQNB1RKR w HFhf -").unwrap();
+    /// assert_eq!(
+    ///     board.display_epd(CastlingNotation::XFen).to_string(),
+    ///     "bqnb1rkr/8/8/8/8/8/8/BQNB1RKR w KQkq -",
+    /// );
+    /// assert_eq!(
+    ///     board.display_epd(CastlingNotation::Shredder).to_string(),
+    ///     "bqnb1rkr/8/8/8/8/8/8/BQNB1RKR w HFhf -",
+    /// );
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn display_epd(&self, castling: CastlingNotation) -> EpdDisplay<'_> {
+        EpdDisplay { board: self, castling, counters: false }
+    }
+
+    /// Get a helper struct to format `self` into a FEN string with the given castling notation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn display_fen(&self, castling: CastlingNotation) -> EpdDisplay<'_> {
+        EpdDisplay { board: self, castling, counters: true }
+    }
+
+    /// Get a helper struct to format `self` into a FEN string. The castling field is written in
+    /// X-FEN, which is the same as the usual notation for normal chess.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let initial = Board::default();
+    /// assert_eq!(initial.to_fen().to_string(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn to_fen(&self) -> EpdDisplay<'_> {
+        self.display_fen(CastlingNotation::XFen)
+    }
+
+    fn write_castling(&self, f: &mut fmt::Formatter<'_>, notation: CastlingNotation) -> fmt::Result {
+        let mut i = 0;
+
+        for color in Color::ALL {
+            let rights = self.castle_rights_of(color);
+            for (allowed, file, king_side, ch) in [
+                (rights.king_side(), rights.king_side_file(), true, 'K'),
+                (rights.queen_side(), rights.queen_side_file(), false, 'Q'),
+            ] {
+                if !allowed { continue }
+                i += 1;
+
+                let ch = if notation == CastlingNotation::Shredder
+                    || (self.chess960 && self.outermost_rook(color, king_side) != Some(file))
+                {
+                    (b'A' + file as u8) as char
+                } else {
+                    ch
+                };
+
+                match color {
+                    Color::White => write!(f, "{ch}")?,
+                    Color::Black => write!(f, "{}", ch.to_ascii_lowercase())?,
+                }
+            }
+        }
+
+        if i == 0 { write!(f, "-")? }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EpdDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let board = self.board;
+
         for rank in Rank::ALL.into_iter().rev() {
             let mut empty = 0;
 
             for file in File::ALL {
-                if let Some((piece, color)) = self.piece_and_color_on(Square::new(file, rank)) {
+                if let Some((piece, color)) = board.piece_and_color_on(Square::new(file, rank)) {
                     if empty != 0 { write!(f, "{empty}")? }
                     write!(f, "{}", piece.to_char(color))?;
                     empty = 0;
@@ -169,19 +368,302 @@ impl fmt::Display for Board {
             if rank != Rank::_1 { write!(f, "/")? }
         }
 
-        write!(f, " {} ", self.side_to_move())?;
-
-        let mut i = 0;
-        if self.castle_rights[0].king_side()  { i += 1; write!(f, "K")? }
-        if self.castle_rights[0].queen_side() { i += 1; write!(f, "Q")? }
-        if self.castle_rights[1].king_side()  { i += 1; write!(f, "k")? }
-        if self.castle_rights[1].queen_side() { i += 1; write!(f, "q")? }
-        if i == 0 { write!(f, "-")? }
+        write!(f, " {} ", board.side_to_move())?;
+        board.write_castling(f, self.castling)?;
 
-        if let Some(file) = self.en_passant {
-            write!(f, " {file}{}", pawn::ep_target_rank(self.side_to_move()))
+        if let Some(file) = board.en_passant {
+            write!(f, " {file}{}", pawn::ep_target_rank(board.side_to_move()))?;
         } else {
-            write!(f, " -")
+            write!(f, " -")?;
+        }
+
+        if self.counters {
+            write!(f, " {} {}", board.halfmove_clock, board.fullmove_number)?;
         }
+
+        Ok(())
+    }
+}
+
+/// Format `self` into an EPD string. The castling field is written in X-FEN, which is the same as
+/// the usual notation for normal chess.
+///
+/// # Example
+/// ```
+/// # use dychess::prelude::*;
+/// #
+/// let initial = Board::default();
+/// assert_eq!(initial.to_string(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -");
+/// ```
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_epd(CastlingNotation::XFen).fmt(f)
+    }
+}
+
+/// An EPD record, which is a position followed by a list of operations.
+///
+/// Operations are separated by semicolons, with each of them made of an opcode followed by its
+/// operands. The perft suite style records (e.g. `... w KQkq - 0 1 ;D1 20 ;D2 400`) are accepted
+/// as well, where the halfmove clock and fullmove number before the operations are parsed into
+/// the board.
+///
+/// # Example
+/// ```
+/// # use dychess::{board::epd::EpdRecord, prelude::*};
+/// #
+/// let record = EpdRecord::parse(
+///     false,
+///     r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#,
+/// ).unwrap();
+///
+/// assert_eq!(record.id(), Some("WAC.001"));
+/// assert_eq!(record.best_moves().collect::<Vec<_>>(), ["Qg6"]);
+///
+/// let record = EpdRecord::parse(
+///     false,
+///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ;D1 20 ;D2 400",
+/// ).unwrap();
+///
+/// assert_eq!(record.perft(2), Some(400));
+/// assert_eq!(record.perft_counts().collect::<Vec<_>>(), [(1, 20), (2, 400)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpdRecord<'a> {
+    board: Board,
+    operations: &'a str,
+}
+
+/// An EPD operation, made of an opcode and its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpdOperation<'a> {
+    opcode: &'a str,
+    operands: &'a str,
+}
+
+/// An iterator over the operations of an [`EpdRecord`].
+#[derive(Debug, Clone)]
+pub struct EpdOperations<'a>(&'a str);
+
+/// An iterator over the operands of an [`EpdOperation`]. Quoted operands are yielded without the
+/// quotes.
+#[derive(Debug, Clone)]
+pub struct EpdOperands<'a>(&'a str);
+
+impl<'a> EpdRecord<'a> {
+    /// Create a new EPD record from a board and a string of operations, e.g. `bm e4; id "a";`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(board: Board, operations: &'a str) -> Self {
+        Self { board, operations }
+    }
+
+    /// Parse an EPD record. See [`Board::from_epd`] for the parsing of the position.
+    ///
+    /// # Errors
+    /// This function errors if the position is not valid enough to make the parser parse it, or if
+    /// a quoted operand is not closed.
+    pub fn parse(chess960: bool, epd: &'a str) -> Result<Self, EpdError> {
+        let mut chars = epd.chars();
+        let mut board = Board::parse_epd(chess960, &mut chars)?;
+        let mut operations = chars.as_str();
+
+        for counter in [&mut board.halfmove_clock, &mut board.fullmove_number] {
+            let rest = operations.trim_start();
+            let end = rest.find(|c: char| c.is_ascii_whitespace() || c == ';').unwrap_or(rest.len());
+            let Ok(value) = rest[..end].parse() else { break };
+
+            *counter = value;
+            operations = &rest[end..];
+        }
+        board.fullmove_number = board.fullmove_number.max(1);
+
+        if operations.chars().filter(|c| *c == '"').count() % 2 != 0 {
+            return Err(EpdError::UnexpectedEnd);
+        }
+
+        Ok(Self { board, operations })
+    }
+
+    /// Get the position of this record.
+    #[inline(always)]
+    #[must_use]
+    pub const fn board(&self) -> &Board { &self.board }
+
+    /// Get an iterator over the operations of this record.
+    #[inline(always)]
+    #[must_use]
+    pub const fn operations(&self) -> EpdOperations<'a> { EpdOperations(self.operations) }
+
+    /// Get the first operation with the given opcode.
+    #[must_use]
+    pub fn operation(&self, opcode: &str) -> Option<EpdOperation<'a>> {
+        self.operations().find(|op| op.opcode() == opcode)
+    }
+
+    /// Get the operands of the first operation with the given opcode, or an empty iterator if there
+    /// are none.
+    #[must_use]
+    pub fn operands(&self, opcode: &str) -> EpdOperands<'a> {
+        self.operation(opcode).map_or(EpdOperands(""), |op| op.operands())
+    }
+
+    /// Get the first operand of the first operation with the given opcode.
+    #[must_use]
+    pub fn operand(&self, opcode: &str) -> Option<&'a str> {
+        self.operands(opcode).next()
+    }
+
+    /// Get the position identifier (`id`).
+    #[inline(always)]
+    #[must_use]
+    pub fn id(&self) -> Option<&'a str> { self.operand("id") }
+
+    /// Get the best moves (`bm`) in SAN.
+    #[inline(always)]
+    #[must_use]
+    pub fn best_moves(&self) -> EpdOperands<'a> { self.operands("bm") }
+
+    /// Get the moves to avoid (`am`) in SAN.
+    #[inline(always)]
+    #[must_use]
+    pub fn avoid_moves(&self) -> EpdOperands<'a> { self.operands("am") }
+
+    /// Get the predicted variation (`pv`) in SAN.
+    #[inline(always)]
+    #[must_use]
+    pub fn predicted_variation(&self) -> EpdOperands<'a> { self.operands("pv") }
+
+    /// Get the centipawn evaluation (`ce`).
+    #[inline(always)]
+    #[must_use]
+    pub fn centipawn_eval(&self) -> Option<i32> { self.operand("ce")?.parse().ok() }
+
+    /// Get the analysis count depth (`acd`).
+    #[inline(always)]
+    #[must_use]
+    pub fn analysis_depth(&self) -> Option<u32> { self.operand("acd")?.parse().ok() }
+
+    /// Get the comment with the given index (`c0` to `c9`).
+    #[must_use]
+    pub fn comment(&self, idx: u8) -> Option<&'a str> {
+        self.operations()
+            .find(|op| op.opcode().strip_prefix('c').and_then(|i| i.parse().ok()) == Some(idx))?
+            .operands()
+            .next()
+    }
+
+    /// Get the expected perft result of the given depth (`D<depth>`).
+    #[must_use]
+    pub fn perft(&self, depth: usize) -> Option<u64> {
+        self.perft_counts().find(|(d, _)| *d == depth).map(|(_, nodes)| nodes)
+    }
+
+    /// Get an iterator over the expected perft results (`D<depth>`) as `(depth, nodes)`.
+    pub fn perft_counts(&self) -> impl Iterator<Item = (usize, u64)> + 'a {
+        self.operations().filter_map(|op| Some((
+            op.opcode().strip_prefix('D')?.parse().ok()?,
+            op.operands().next()?.parse().ok()?,
+        )))
+    }
+}
+
+impl<'a> EpdOperation<'a> {
+    /// Create a new operation. The operands are separated by whitespaces and may be quoted.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(opcode: &'a str, operands: &'a str) -> Self {
+        Self { opcode, operands }
+    }
+
+    /// Get the opcode of this operation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn opcode(&self) -> &'a str { self.opcode }
+
+    /// Get an iterator over the operands of this operation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn operands(&self) -> EpdOperands<'a> { EpdOperands(self.operands) }
+}
+
+impl<'a> Iterator for EpdOperations<'a> {
+    type Item = EpdOperation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.0.trim_start();
+            if rest.is_empty() { return None }
+
+            let mut quoted = false;
+            let end = rest.find(|c| {
+                if c == '"' { quoted = !quoted }
+                c == ';' && !quoted
+            }).unwrap_or(rest.len());
+
+            let op = rest[..end].trim_end();
+            self.0 = rest.get(end + 1..).unwrap_or("");
+
+            if op.is_empty() { continue }
+
+            let (opcode, operands) = op.split_once(|c: char| c.is_ascii_whitespace()).unwrap_or((op, ""));
+            return Some(EpdOperation { opcode, operands: operands.trim_start() });
+        }
+    }
+}
+
+impl core::iter::FusedIterator for EpdOperations<'_> {}
+
+impl<'a> EpdOperands<'a> {
+    /// Split off the next operand, including the quotes if it's quoted.
+    fn next_raw(&mut self) -> Option<&'a str> {
+        let rest = self.0.trim_start();
+        if rest.is_empty() { return None }
+
+        let end = rest.strip_prefix('"').map_or_else(
+            || rest.find(|c: char| c.is_ascii_whitespace()).unwrap_or(rest.len()),
+            |quoted| quoted.find('"').map_or(rest.len(), |i| i + 2),
+        );
+
+        self.0 = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+impl<'a> Iterator for EpdOperands<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let operand = self.next_raw()?;
+
+        Some(operand.strip_prefix('"').map_or(operand, |quoted| quoted.strip_suffix('"').unwrap_or(quoted)))
+    }
+}
+
+impl core::iter::FusedIterator for EpdOperands<'_> {}
+
+impl fmt::Display for EpdOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode)?;
+
+        let mut operands = self.operands();
+        while let Some(operand) = operands.next_raw() {
+            write!(f, " {operand}")?;
+        }
+
+        write!(f, ";")
+    }
+}
+
+/// Format `self` into an EPD string with its operations.
+impl fmt::Display for EpdRecord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board)?;
+
+        for op in self.operations() {
+            write!(f, " {op}")?;
+        }
+
+        Ok(())
     }
 }
@@ -2,6 +2,10 @@ use crate::prelude::*;
 
 pub mod epd;
 pub mod movegen;
+pub mod san;
+pub mod see;
+pub mod uci;
+pub(crate) mod cuckoo;
 mod util;
 mod zobrist;
 
@@ -19,8 +23,19 @@ pub struct Board {
     castle_rights: [CastleRights; 2],
 
     hash: u64,
+    /// Hash of the pawns only.
+    pawn_hash: u64,
+    /// Hash of the number of each piece of each color.
+    material_hash: u64,
+    /// Hashes of the pieces other than pawns for white and black.
+    non_pawn_hashes: [u64; 2],
     en_passant: Option<File>,
 
+    /// Number of plies since the last capture or pawn move, used for the 50-move rule.
+    halfmove_clock: u16,
+    /// Number of full moves, starting at 1 and incremented after black moves.
+    fullmove_number: u16,
+
     chess960: bool,
 }
 
@@ -37,78 +52,167 @@ impl Default for Board {
 impl Board {
     /// Make a move on the board.
     ///
+    /// # Castling
+    /// In normal chess, castling moves are encoded as the king moving 2 squares towards the rook
+    /// (e.g. `e1g1`). In Chess960, castling moves are encoded as the king capturing its own rook
+    /// (e.g. `b1a1`).
+    ///
+    /// # Returns
+    /// This returns a [`MoveRestorer`] to undo this move by calling [`Self::unmake_move`], which
+    /// can be ignored if the board is copied before making the move instead.
+    ///
     /// # Panics
     /// This function panics if the move's `from` square is empty.
-    pub fn make_move(&mut self, mov: Move) {
+    pub fn make_move(&mut self, mov: Move) -> MoveRestorer {
+        let mut restorer = MoveRestorer {
+            captured: None,
+            castling: false,
+            castle_rights: self.castle_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            hash: self.hash,
+        };
+
+        let us = self.side_to_move();
         let move_bb = Bitboard::from(mov.from()) | mov.to().into();
+        let our_rights = self.castle_rights_of(us);
         let (piece, _) = self.erase_piece(mov.from())
             .expect("tried to make invalid move: piece does not exist on move `from` square");
-        let capture = self.place_piece(self.side_to_move(), mov.to(), mov.promotion().unwrap_or(piece));
+
+        let castle_rook = if piece == Piece::King { self.castling_rook(us, mov) } else { None };
+
+        restorer.castling = castle_rook.is_some();
+        let capture = if let Some(rook_at) = castle_rook {
+            let (king_to, rook_to) = king::castle_to(us, mov.from().file(), rook_at.file());
+
+            self.erase_piece(rook_at);
+            self.place_piece(us, king_to, Piece::King);
+            self.place_piece(us, rook_to, Piece::Rook);
+            None
+        } else {
+            self.place_piece(us, mov.to(), mov.promotion().unwrap_or(piece))
+        };
 
         match piece {
             Piece::Pawn => if let Some(ep) = self.en_passant {
-                if mov.from().file() != mov.to().file() && mov.to().file() == ep && !(pawn::ep_targets(self.side_to_move()) & mov.to().into()).is_empty() {
+                if mov.from().file() != mov.to().file() && mov.to().file() == ep && !(pawn::ep_targets(us) & mov.to().into()).is_empty() {
                     self.erase_piece(Square::new(ep, mov.from().rank()));
+                    restorer.captured = Some(Piece::Pawn);
                 }
             },
             Piece::Rook => {
-                if mov.from() == Square::new(File::H, self.side_to_move().back_rank()) {
-                    self.disallow_king_side_castle(self.side_to_move());
-                } else if mov.from() == Square::new(File::A, self.side_to_move().back_rank()) {
-                    self.disallow_queen_side_castle(self.side_to_move());
+                if mov.from() == Square::new(our_rights.king_side_file(), us.back_rank()) {
+                    self.disallow_king_side_castle(us);
+                } else if mov.from() == Square::new(our_rights.queen_side_file(), us.back_rank()) {
+                    self.disallow_queen_side_castle(us);
                 }
             },
             Piece::King => {
-                self.disallow_queen_side_castle(self.side_to_move());
-                self.disallow_king_side_castle(self.side_to_move());
-
-                if self.chess960 && capture == Some((Piece::Rook, self.side_to_move())) {
-                    todo!("chess960 castling");
-                } else if !self.chess960 && (move_bb & king::CASTLE_MOVE) == move_bb {
-                    const ROOK_AT: [File; 8] = [
-                        File::A,
-                        File::A,
-                        File::A,
-                        File::A,
-                        File::H,
-                        File::H,
-                        File::H,
-                        File::H,
-                    ];
-                    const ROOK_TO: [File; 8] = [
-                        File::D,
-                        File::D,
-                        File::D,
-                        File::D,
-                        File::F,
-                        File::F,
-                        File::F,
-                        File::F,
-                    ];
-
-                    let rook_at = Square::new(ROOK_AT[mov.to().file() as usize], mov.to().rank());
-                    let rook_to = Square::new(ROOK_TO[mov.to().file() as usize], mov.to().rank());
-                    self.erase_piece(rook_at);
-                    self.place_piece(self.side_to_move(), rook_to, Piece::Rook);
-                }
+                self.disallow_queen_side_castle(us);
+                self.disallow_king_side_castle(us);
             },
             _ => {},
         }
 
-        if capture == Some((Piece::Rook, !self.side_to_move())) {
-            if mov.to() == Square::new(File::H, (!self.side_to_move()).back_rank()) {
-                self.disallow_king_side_castle(!self.side_to_move());
-            } else if mov.to() == Square::new(File::A, (!self.side_to_move()).back_rank()) {
-                self.disallow_queen_side_castle(!self.side_to_move());
+        if capture == Some((Piece::Rook, !us)) {
+            let their_rights = self.castle_rights_of(!us);
+
+            if mov.to() == Square::new(their_rights.king_side_file(), (!us).back_rank()) {
+                self.disallow_king_side_castle(!us);
+            } else if mov.to() == Square::new(their_rights.queen_side_file(), (!us).back_rank()) {
+                self.disallow_queen_side_castle(!us);
             }
         }
 
-        self.en_passant = (piece == Piece::Pawn && (move_bb & pawn::double_pushes(self.side_to_move())) == move_bb)
+        self.en_passant = (piece == Piece::Pawn && (move_bb & pawn::double_pushes(us)) == move_bb)
             .then_some(mov.from().file());
-        self.side_to_move = !self.side_to_move();
+        self.halfmove_clock = if piece == Piece::Pawn || capture.is_some() {
+            0
+        } else {
+            self.halfmove_clock.saturating_add(1)
+        };
+        self.fullmove_number = self.fullmove_number.saturating_add((us == Color::Black) as u16);
+        self.side_to_move = !us;
         self.hash ^= zobrist::SIDE_TO_MOVE;
+
+        if let Some((piece, _)) = capture {
+            restorer.captured = Some(piece);
+        }
+
+        restorer
+    }
+
+    /// Undo a move that was made by [`Self::make_move`].
+    ///
+    /// # Notes
+    /// The move and restorer must be the ones from the last [`Self::make_move`] call on this board,
+    /// otherwise the board might end up in an invalid state.
+    ///
+    /// # Panics
+    /// This function panics if the move's `to` square is empty and the move is not castling.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn unmake_move(&mut self, mov: Move, restorer: MoveRestorer) {
+        let us = !self.side_to_move();
+
+        if restorer.castling {
+            let rights = restorer.castle_rights[us as usize];
+            let rook_at = if self.chess960 {
+                mov.to()
+            } else {
+                Square::new(
+                    if mov.to().file() > mov.from().file() { rights.king_side_file() } else { rights.queen_side_file() },
+                    mov.to().rank(),
+                )
+            };
+            let (king_to, rook_to) = king::castle_to(us, mov.from().file(), rook_at.file());
+
+            self.erase_piece(king_to);
+            self.erase_piece(rook_to);
+            self.place_unchecked(us, mov.from(), Piece::King);
+            self.place_unchecked(us, rook_at, Piece::Rook);
+        } else {
+            let (piece, _) = self.erase_piece(mov.to())
+                .expect("tried to unmake invalid move: piece does not exist on move `to` square");
+            let piece = if mov.promotion().is_some() { Piece::Pawn } else { piece };
+            self.place_unchecked(us, mov.from(), piece);
+
+            if let Some(captured) = restorer.captured {
+                let is_ep = piece == Piece::Pawn
+                    && restorer.en_passant == Some(mov.to().file())
+                    && mov.from().file() != mov.to().file()
+                    && mov.to().rank() == pawn::ep_target_rank(us);
+                let square = if is_ep { Square::new(mov.to().file(), mov.from().rank()) } else { mov.to() };
+
+                self.place_unchecked(!us, square, captured);
+            }
+        }
+
+        self.castle_rights = restorer.castle_rights;
+        self.en_passant = restorer.en_passant;
+        self.halfmove_clock = restorer.halfmove_clock;
+        self.fullmove_number = restorer.fullmove_number;
+        self.side_to_move = us;
+        self.hash = restorer.hash;
+    }
+
+    /// Make a move on a copy of the board and return the copy.
+    ///
+    /// # Panics
+    /// See [`Self::make_move`].
+    #[inline(always)]
+    #[must_use]
+    pub fn copy_make_move(&self, mov: Move) -> Self {
+        let mut board = *self;
+        board.make_move(mov);
+        board
     }
 
+    /// Get if this board is a Chess960 board.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_chess960(&self) -> bool { self.chess960 }
+
     pub(crate) const fn allow_queen_side_castle(&mut self, color: Color) {
         if !self.castle_rights[color as usize].queen_side() {
             self.hash ^= zobrist::CASTLE[color as usize * 2 + 1];
@@ -158,6 +262,34 @@ impl Board {
         })
     }
 
+    /// Get a hash of only the pawns of both colors, for caching pawn structure evaluation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn pawn_hash(&self) -> u64 { self.pawn_hash }
+
+    /// Get a hash of the number of each piece of each color, regardless of where they are, for
+    /// caching material evaluation.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let a = Board::from_epd(false, "4k3/8/8/8/8/8/8/RN2K3 w - -").unwrap();
+    /// let b = Board::from_epd(false, "4k3/8/3N4/8/8/8/8/4K2R b - -").unwrap();
+    /// let c = Board::from_epd(false, "4k3/8/8/8/8/8/8/RB2K3 w - -").unwrap();
+    ///
+    /// assert_eq!(a.material_hash(), b.material_hash());
+    /// assert_ne!(a.material_hash(), c.material_hash());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn material_hash(&self) -> u64 { self.material_hash }
+
+    /// Get a hash of the pieces other than pawns of a color, including the king.
+    #[inline(always)]
+    #[must_use]
+    pub const fn non_pawn_hash(&self, color: Color) -> u64 { self.non_pawn_hashes[color as usize] }
+
     /// Pass this move to the side to move.
     ///
     /// # Notes
@@ -168,12 +300,16 @@ impl Board {
     /// [`Self::restore_passed`].
     #[inline(always)]
     pub fn null_move(&mut self) -> NullMoveRestorer {
+        let restorer = NullMoveRestorer {
+            en_passant: core::mem::take(&mut self.en_passant),
+            fullmove_number: self.fullmove_number,
+        };
+
+        self.fullmove_number = self.fullmove_number.saturating_add((self.side_to_move() == Color::Black) as u16);
         self.side_to_move = !self.side_to_move();
         self.hash ^= zobrist::SIDE_TO_MOVE;
 
-        NullMoveRestorer {
-            en_passant: core::mem::take(&mut self.en_passant),
-        }
+        restorer
     }
 
     /// Restore a passed move that was made by [`Self::pass_move`].
@@ -184,6 +320,7 @@ impl Board {
         self.hash ^= zobrist::SIDE_TO_MOVE;
 
         self.en_passant = restorer.en_passant;
+        self.fullmove_number = restorer.fullmove_number;
     }
 
     #[inline(always)]
@@ -199,7 +336,10 @@ impl Board {
         self.pieces[piece as usize] |= to_bb;
         self.colors[color as usize] |= to_bb;
         self.mailbox[square.to_usize()] = mailbox_element(color, piece);
-        self.hash ^= zobrist::piece(piece, color, square);
+
+        let key = zobrist::piece(piece, color, square);
+        self.hash ^= key;
+        self.update_partial_hashes::<true>(color, piece, key);
     }
 
     #[inline(always)]
@@ -212,12 +352,32 @@ impl Board {
             self.pieces[piece as usize] ^= bb;
             self.colors[color as usize] ^= bb;
             self.mailbox[square.to_usize()] = 0;
-            self.hash ^= zobrist::piece(piece, color, square);
+
+            let key = zobrist::piece(piece, color, square);
+            self.hash ^= key;
+            self.update_partial_hashes::<false>(color, piece, key);
         }
 
         piece
     }
 
+    /// Toggle a piece with the given square key in the pawn, material and non-pawn hashes, after it
+    /// is placed if `PLACED` or erased otherwise.
+    #[inline(always)]
+    fn update_partial_hashes<const PLACED: bool>(&mut self, color: Color, piece: Piece, key: u64) {
+        if piece == Piece::Pawn {
+            self.pawn_hash ^= key;
+        } else {
+            self.non_pawn_hashes[color as usize] ^= key;
+        }
+
+        // key the piece by its index among the pieces of the same kind, which is the count before
+        // placing it or after erasing it
+        let count = (self.pieces[piece as usize] & self.colors[color as usize]).popcnt();
+        let index = if PLACED { count - 1 } else { count };
+        self.material_hash ^= zobrist::piece(piece, color, Square::from_index(index as u8));
+    }
+
     pub(crate) fn piece_targets<const ATKDEF: bool>(&self, color: Color, piece: Piece, sq: Square) -> Bitboard {
         let bb = match piece {
             Piece::Pawn => {
@@ -235,38 +395,73 @@ impl Board {
             Piece::Bishop => bishop::moves(sq, self.combined()),
             Piece::Rook => rook::moves(sq, self.combined()),
             Piece::Queen => queen::moves(sq, self.combined()),
-            Piece::King => {
-                let mut moves = king::moves(sq);
-
-                if !ATKDEF && self.castle_rights_of(color).king_side() {
-                    if let Some(ks_rook) = (self.rooks_of(color) & self.castle_rights_of(color).king_side_file().into() & color.back_rank().into()).first_square() {
-                        if (king::castle_clearance(color, sq.file(), ks_rook.file()) & self.combined()).is_empty()
-                            && (king::castle_path(color, sq.file(), ks_rook.file()) & self.side_attack_def(!color)).is_empty()
-                        {
-                            moves |= Bitboard::from(if self.chess960 { ks_rook} else { Square::new(File::G, sq.rank()) });
-                        }
-                    }
-                }
-
-                if !ATKDEF && self.castle_rights_of(color).queen_side() {
-                    if let Some(qs_rook) = (self.rooks_of(color) & self.castle_rights_of(color).queen_side_file().into() & color.back_rank().into()).first_square() {
-                        if (king::castle_clearance(color, sq.file(), qs_rook.file()) & self.combined()).is_empty()
-                            && (king::castle_path(color, sq.file(), qs_rook.file()) & self.side_attack_def(!color)).is_empty()
-                        {
-                            moves |= Bitboard::from(if self.chess960 { qs_rook } else { Square::new(File::C, sq.rank()) });
-                        }
-                    }
-                }
-
-                moves
-            },
+            Piece::King => king::moves(sq),
         };
 
         if ATKDEF {
             bb
+        } else if piece == Piece::King {
+            (bb & !self.color_combined(color)) | self.castle_targets(color, sq, self.side_attack_def(!color))
+        } else {
+            bb & !self.color_combined(color)
+        }
+    }
+
+    /// Get the square of the rook to castle with if the given side's king makes the move, or `None`
+    /// if the move is not castling.
+    #[inline(always)]
+    pub(crate) fn castling_rook(&self, color: Color, mov: Move) -> Option<Square> {
+        let move_bb = Bitboard::from(mov.from()) | mov.to().into();
+        let rights = self.castle_rights_of(color);
+
+        if self.chess960 {
+            (self.piece_and_color_on(mov.to()) == Some((Piece::Rook, color))).then_some(mov.to())
+        } else if (move_bb & king::CASTLE_MOVE) == move_bb {
+            Some(Square::new(
+                if mov.to().file() > mov.from().file() { rights.king_side_file() } else { rights.queen_side_file() },
+                mov.to().rank(),
+            ))
         } else {
-            bb & !self.color_combined(self.side_to_move())
+            None
+        }
+    }
+
+    /// Get the castling targets of the king, given the squares attacked by the opponent.
+    pub(crate) fn castle_targets(&self, color: Color, king_sq: Square, attacked: Bitboard) -> Bitboard {
+        let rights = self.castle_rights_of(color);
+        let mut targets = Bitboard::default();
+
+        for (allowed, rook_file) in [
+            (rights.king_side(), rights.king_side_file()),
+            (rights.queen_side(), rights.queen_side_file()),
+        ] {
+            if !allowed { continue }
+
+            let rook_sq = Square::new(rook_file, color.back_rank());
+            if (self.rooks_of(color) & rook_sq.into()).is_empty() { continue }
+
+            if !(king::castle_clearance(color, king_sq.file(), rook_file) & self.combined()).is_empty()
+                || !(king::castle_path(color, king_sq.file(), rook_file) & attacked).is_empty()
+            {
+                continue;
+            }
+
+            let (king_to, _) = king::castle_to(color, king_sq.file(), rook_file);
+
+            if self.chess960 {
+                // the castling rook might be shielding the king's destination from a slider
+                let occupied = self.combined() ^ Bitboard::from(king_sq) ^ rook_sq.into();
+                let their_rooks = self.rooks_of(!color) | self.queens_of(!color);
+
+                if !(rook::moves(king_to, occupied) & their_rooks).is_empty() { continue }
+
+                targets |= Bitboard::from(rook_sq);
+            } else {
+                targets |= Bitboard::from(king_to);
+            }
         }
+
+        targets
     }
 
     #[inline(always)]
@@ -294,6 +489,137 @@ impl Board {
         self.is_side_check(self.side_to_move())
     }
 
+    /// Get if a pseudo-legal move gives check to the opponent, without making the move. This covers
+    /// direct checks, discovered checks, promotions, en passant and castling.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "5k2/8/8/8/8/8/8/R3K2R w KQ -").unwrap();
+    ///
+    /// assert!(board.gives_check(Move::new(Square::E1, Square::G1, None)));
+    /// assert!(board.gives_check(Move::new(Square::A1, Square::A8, None)));
+    /// assert!(!board.gives_check(Move::new(Square::E1, Square::C1, None)));
+    /// ```
+    #[must_use]
+    pub fn gives_check(&self, mov: Move) -> bool {
+        let (from, to) = (mov.from(), mov.to());
+        let Some((piece, us)) = self.piece_and_color_on(from) else { return false };
+        let their_king = self.king_of(!us);
+        let our_rooks = self.rooks_of(us) | self.queens_of(us);
+        let our_bishops = self.bishops_of(us) | self.queens_of(us);
+
+        if piece == Piece::King {
+            if let Some(rook_sq) = self.castling_rook(us, mov) {
+                let (king_to, rook_to) = king::castle_to(us, from.file(), rook_sq.file());
+                let occupied = (self.combined() ^ from.into() ^ rook_sq.into()) | king_to.into() | rook_to.into();
+
+                return !(rook::moves(rook_to, occupied) & their_king.into()).is_empty();
+            }
+        }
+
+        // discovered check by moving off the line to the king
+        if !(self.discovered_check_candidates() & from.into()).is_empty()
+            && (Bitboard::line(their_king, from) & to.into()).is_empty()
+        {
+            return true;
+        }
+
+        let mut occupied = (self.combined() ^ from.into()) | to.into();
+
+        // en passant may discover a check through the captured pawn
+        if piece == Piece::Pawn && from.file() != to.file() && self.piece_on(to).is_none() {
+            occupied ^= Square::new(to.file(), from.rank()).into();
+
+            if !(bishop::moves(their_king, occupied) & our_bishops).is_empty()
+                || !(rook::moves(their_king, occupied) & our_rooks).is_empty()
+            {
+                return true;
+            }
+        }
+
+        let attacks = match mov.promotion().unwrap_or(piece) {
+            Piece::Pawn => pawn::captures(us, to),
+            Piece::Knight => knight::moves(to),
+            Piece::Bishop => bishop::moves(to, occupied),
+            Piece::Rook => rook::moves(to, occupied),
+            Piece::Queen => queen::moves(to, occupied),
+            Piece::King => Bitboard::default(),
+        };
+
+        !(attacks & their_king.into()).is_empty()
+    }
+
+    /// Get if neither side has enough material to checkmate by any sequence of legal moves,
+    /// following the FIDE rules. This is the case if only kings and either at most 1 minor piece,
+    /// or any number of bishops that are all on squares of the same color, are left.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/2B1K3 w - -").unwrap();
+    /// assert!(board.is_insufficient_material());
+    ///
+    /// // bishops on light squares only
+    /// let board = Board::from_epd(false, "2b1k3/8/8/8/8/8/8/3BKB2 w - -").unwrap();
+    /// assert!(board.is_insufficient_material());
+    ///
+    /// // a helpmate is possible
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/1N2KN2 w - -").unwrap();
+    /// assert!(!board.is_insufficient_material());
+    /// ```
+    #[must_use]
+    pub fn is_insufficient_material(&self) -> bool {
+        if !(self.pawns() | self.rooks() | self.queens()).is_empty() { return false }
+
+        let minors = self.knights() | self.bishops();
+        minors.popcnt() <= 1
+            || (self.knights().is_empty()
+                && ((self.bishops() & Bitboard::LIGHT_SQUARES).is_empty()
+                    || (self.bishops() & Bitboard::DARK_SQUARES).is_empty()))
+    }
+
+    /// Get if the given side can't checkmate even with the help of the opponent. This is stricter
+    /// than [`Self::is_insufficient_material`], and is used for adjudicating a timeout as a draw
+    /// instead of a loss.
+    ///
+    /// A side with a single knight can only checkmate if the opponent has pieces other than queens
+    /// to block its king, and a side with only bishops on squares of the same color can only
+    /// checkmate if the opponent has pawns or knights, or a bishop on the other color.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/1N2K3 w - -").unwrap();
+    /// assert!(board.cannot_win(Color::White));
+    ///
+    /// // the pawn can block its own king
+    /// let board = Board::from_epd(false, "4k3/4p3/8/8/8/8/8/1N2K3 w - -").unwrap();
+    /// assert!(!board.cannot_win(Color::White));
+    /// assert!(!board.cannot_win(Color::Black));
+    /// ```
+    #[must_use]
+    pub fn cannot_win(&self, color: Color) -> bool {
+        let ours = self.color_combined(color);
+        if !(ours & (self.pawns() | self.rooks() | self.queens())).is_empty() { return false }
+
+        if !(ours & self.knights()).is_empty() {
+            let their_blockers = self.color_combined(!color) & !self.kings() & !self.queens();
+            return ours.popcnt() <= 2 && their_blockers.is_empty();
+        }
+
+        if !(ours & self.bishops()).is_empty() {
+            let same_color = (self.bishops() & Bitboard::LIGHT_SQUARES).is_empty()
+                || (self.bishops() & Bitboard::DARK_SQUARES).is_empty();
+            return same_color && (self.pawns() | self.knights()).is_empty();
+        }
+
+        true
+    }
+
     /// Get if the side just moved is in check. This will indicate if the pseudo-legal move
     /// previously moved is actually legal.
     #[inline(always)]
@@ -338,16 +664,128 @@ impl Board {
         atkdef
     }
 
+    /// Get the squares attacked by the given side, as if the pieces on the board are `occupied`.
+    #[must_use]
+    pub(crate) fn attacked_squares(&self, color: Color, occupied: Bitboard) -> Bitboard {
+        let mut attacked = Bitboard::default();
+
+        for sq in self.pawns_of(color) {
+            attacked |= pawn::captures(color, sq);
+        }
+        for sq in self.knights_of(color) {
+            attacked |= knight::moves(sq);
+        }
+        for sq in self.bishops_of(color) | self.queens_of(color) {
+            attacked |= bishop::moves(sq, occupied);
+        }
+        for sq in self.rooks_of(color) | self.queens_of(color) {
+            attacked |= rook::moves(sq, occupied);
+        }
+
+        attacked | king::moves(self.king_of(color))
+    }
+
+    /// Get the pieces of the given side that are pinned to its king, and the opponent's pieces that
+    /// are pinning them.
+    #[must_use]
+    pub(crate) fn pins(&self, color: Color) -> (Bitboard, Bitboard) {
+        let (blockers, pinners) = self.slider_blockers(color);
+        (blockers & self.color_combined(color), pinners)
+    }
+
+    /// Get the pieces of the given side that are pinned to its king.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/4r3/8/8/8/8/4N3/4K3 w - -").unwrap();
+    ///
+    /// assert_eq!(board.pinned(Color::White), Square::E2.into());
+    /// assert_eq!(board.pinners(Color::Black), Square::E7.into());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn pinned(&self, color: Color) -> Bitboard {
+        self.pins(color).0
+    }
+
+    /// Get the sliders of the given side that are pinning an opponent's piece to the opponent's
+    /// king.
+    #[inline(always)]
+    #[must_use]
+    pub fn pinners(&self, color: Color) -> Bitboard {
+        self.slider_blockers(!color).1
+    }
+
+    /// Get the pieces of either side that are the only piece between the king of the given side
+    /// and an opponent's slider. The given side's blockers are pinned, and the opponent's blockers
+    /// can give a discovered check.
+    #[inline(always)]
+    #[must_use]
+    pub fn blockers_for_king(&self, color: Color) -> Bitboard {
+        self.slider_blockers(color).0
+    }
+
+    /// Get the side to move's pieces that give a discovered check when moved off their line to the
+    /// opponent's king.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/4N3/8/8/4RK2 w - -").unwrap();
+    ///
+    /// assert_eq!(board.discovered_check_candidates(), Square::E4.into());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn discovered_check_candidates(&self) -> Bitboard {
+        self.blockers_for_king(!self.side_to_move()) & self.our_pieces()
+    }
+
+    /// Get the pieces of either side that are the only piece between the king of the given side
+    /// and an opponent's slider, and the sliders whose only blocker is the given side's piece.
+    #[must_use]
+    pub(crate) fn slider_blockers(&self, color: Color) -> (Bitboard, Bitboard) {
+        let ksq = self.king_of(color);
+        let occupied = self.combined();
+        let snipers = (rook::rays(ksq) & (self.rooks_of(!color) | self.queens_of(!color)))
+            | (bishop::rays(ksq) & (self.bishops_of(!color) | self.queens_of(!color)));
+
+        let mut blockers = Bitboard::default();
+        let mut pinners = Bitboard::default();
+
+        for sniper in snipers {
+            let between = Bitboard::between(ksq, sniper) & occupied;
+
+            if between.popcnt() == 1 {
+                blockers |= between;
+
+                if !(between & self.color_combined(color)).is_empty() {
+                    pinners |= sniper.into();
+                }
+            }
+        }
+
+        (blockers, pinners)
+    }
+
     /// Get the attackers and defenders of a particular square.
     #[must_use]
     pub fn attackers(&self, sq: Square) -> Bitboard {
-        let combined = self.combined();
+        self.attackers_with(sq, self.combined())
+    }
 
+    /// Get the attackers and defenders of a particular square, as if the pieces on the board are
+    /// `occupied`.
+    #[must_use]
+    pub(crate) fn attackers_with(&self, sq: Square, occupied: Bitboard) -> Bitboard {
         pawn::captures(!Color::White, sq) & self.white_pawns()
             | pawn::captures(!Color::Black, sq) & self.black_pawns()
             | knight::moves(sq) & self.knights()
-            | bishop::moves(sq, combined) & (self.bishops() | self.queens())
-            | rook::moves(sq, combined) & (self.rooks() | self.queens())
+            | bishop::moves(sq, occupied) & (self.bishops() | self.queens())
+            | rook::moves(sq, occupied) & (self.rooks() | self.queens())
             | king::moves(sq) & self.kings()
     }
 
@@ -381,7 +819,20 @@ impl core::hash::Hash for Board {
     }
 }
 
-#[derive(Debug)]
+/// The information needed to undo a move, returned by [`Board::make_move`].
+#[derive(Debug, Clone, Copy)]
+pub struct MoveRestorer {
+    captured: Option<Piece>,
+    castling: bool,
+    castle_rights: [CastleRights; 2],
+    en_passant: Option<File>,
+    halfmove_clock: u16,
+    fullmove_number: u16,
+    hash: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct NullMoveRestorer {
     en_passant: Option<File>,
+    fullmove_number: u16,
 }
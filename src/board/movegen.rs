@@ -1,22 +1,89 @@
-use super::{Bitboard, BitboardIter, Board, Move, Piece, Square, pawn};
+use core::marker::PhantomData;
 
-/// A staged pseudo-legal move generator.
+use super::{Bitboard, BitboardIter, Board, Move, MoveList, Piece, Square, bishop, king, knight, pawn, rook};
+
+const ALL: u8 = 0;
+const CAPTURES: u8 = 1;
+const QUIETS: u8 = 2;
+const QUIET_CHECKS: u8 = 3;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The kind of moves a [`MoveGen`] generates. This trait is sealed, and is only implemented by
+/// [`All`], [`Captures`], [`Quiets`] and [`QuietChecks`].
+pub trait MoveKind: sealed::Sealed + Copy {
+    #[doc(hidden)]
+    const KIND: u8;
+}
+
+/// Generate all moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct All;
+/// Generate only captures, including en passant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Captures;
+/// Generate only non-captures, including castling and non-capturing promotions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quiets;
+/// Generate only non-captures that give check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuietChecks;
+
+macro_rules! impl_move_kind {
+    ($($ty:ident => $kind:ident),*) => {$(
+        impl sealed::Sealed for $ty {}
+        impl MoveKind for $ty {
+            const KIND: u8 = $kind;
+        }
+    )*};
+}
+
+impl_move_kind!(All => ALL, Captures => CAPTURES, Quiets => QUIETS, QuietChecks => QUIET_CHECKS);
+
+/// A staged move generator.
+///
+/// It generates the kind of moves specified by `K` (one of [`All`], [`Captures`], [`Quiets`] and
+/// [`QuietChecks`]), which are pseudo-legal, or strictly legal if `LEGAL` is true.
+///
+/// The moves of the priority list are generated first, and are removed from the targets of their
+/// pieces so that generating the other moves doesn't scan the list. To order all the moves by
+/// scores, use a [`MovePicker`](crate::move_picker::MovePicker) instead.
 #[derive(Clone, Copy)]
-pub struct MoveGen<'a, const CAPTURES: bool> {
+pub struct MoveGen<'a, K: MoveKind = All, const LEGAL: bool = false> {
     board: &'a Board,
+    kind: PhantomData<K>,
     priority: &'a [Move],
     priority_at: usize,
+    /// The `from` squares of the priority moves already generated, excluding promotions.
+    emitted_from: Bitboard,
+    /// The priority promotions already generated, see [`promotion_index`].
+    emitted_promotions: u128,
 
     cur_piece_targets: Bitboard,
     cur_piece_sq: Square,
     cur_promote_to: u8,
 
     pieces: BitboardIter,
+
+    /// The squares non-king pieces can move to in order to resolve checks, which is every square
+    /// when not in check.
+    check_mask: Bitboard,
+    pinned: Bitboard,
+    /// The squares attacked by the opponent, as if our king is not on the board.
+    king_danger: Bitboard,
+
+    /// The squares each piece can move to in order to directly check the opponent's king.
+    check_squares: [Bitboard; 6],
+    /// Our pieces that give a discovered check when moved off their line to the opponent's king.
+    discoverers: Bitboard,
 }
 
 impl Board {
     /// Generate pseudo-legal moves that can be iterated with a list of moves that are prioritized
-    /// over other moves.
+    /// over other moves. When in check, only moves that capture the checker, interpose or move the
+    /// king are generated.
     ///
     /// # Example
     /// ```
@@ -31,71 +98,368 @@ impl Board {
     /// ```
     #[inline(always)]
     #[must_use]
-    pub fn pseudo_legal_moves<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, false> {
-        MoveGen {
-            board: self,
-            priority,
-            priority_at: 0,
-
-            cur_piece_targets: Bitboard::default(),
-            cur_piece_sq: Square::default(),
-            cur_promote_to: 0,
-
-            pieces: self.our_pieces().into_iter(),
-        }
+    pub fn pseudo_legal_moves<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a> {
+        MoveGen::new(self, priority)
     }
 
     /// Generate pseudo-legal captures that can be iterated with a list of moves that are
     /// prioritized over other moves.
     #[inline(always)]
     #[must_use]
-    pub fn pseudo_legal_captures<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, true> {
-        MoveGen {
-            board: self,
+    pub fn pseudo_legal_captures<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, Captures> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate pseudo-legal non-captures that can be iterated with a list of moves that are
+    /// prioritized over other moves. Together with [`Self::pseudo_legal_captures`], every
+    /// pseudo-legal move is generated exactly once.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/3p4/4P3/8/8/4K3 w - -").unwrap();
+    ///
+    /// assert_eq!(board.pseudo_legal_captures(&[]).count(), 1);
+    /// assert_eq!(board.pseudo_legal_quiets(&[]).count(), 6);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn pseudo_legal_quiets<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, Quiets> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate pseudo-legal non-captures that give check, including discovered checks, checking
+    /// promotions and castling into a rook check. This is useful for quiescence search.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "5k2/8/8/8/8/8/8/R3K2R w KQ -").unwrap();
+    ///
+    /// // Ra8+, Rh8+, Rf1+ and O-O+
+    /// assert_eq!(board.pseudo_legal_quiet_checks(&[]).count(), 4);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn pseudo_legal_quiet_checks<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, QuietChecks> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate legal moves that can be iterated with a list of moves that are prioritized over
+    /// other moves. Unlike [`Self::pseudo_legal_moves`], there is no need to check the legality of
+    /// the moves with [`Self::is_illegal`] after making them.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/3q4/4K3 w - -").unwrap();
+    ///
+    /// assert_eq!(board.pseudo_legal_moves(&[]).count(), 5);
+    /// assert_eq!(board.legal_moves(&[]).count(), 2);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn legal_moves<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, All, true> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate legal captures that can be iterated with a list of moves that are prioritized over
+    /// other moves.
+    #[inline(always)]
+    #[must_use]
+    pub fn legal_captures<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, Captures, true> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate legal non-captures that can be iterated with a list of moves that are prioritized
+    /// over other moves.
+    #[inline(always)]
+    #[must_use]
+    pub fn legal_quiets<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, Quiets, true> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Generate legal non-captures that give check that can be iterated with a list of moves that
+    /// are prioritized over other moves.
+    #[inline(always)]
+    #[must_use]
+    pub fn legal_quiet_checks<'a>(&'a self, priority: &'a [Move]) -> MoveGen<'a, QuietChecks, true> {
+        MoveGen::new(self, priority)
+    }
+
+    /// Get if a move is pseudo-legal, which is exactly when [`Self::pseudo_legal_moves`] would
+    /// generate it. This never panics, even if the move is not valid in this position.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::default();
+    ///
+    /// assert!(board.is_pseudo_legal(Move::new(Square::E2, Square::E4, None)));
+    /// assert!(!board.is_pseudo_legal(Move::new(Square::E3, Square::E4, None)));
+    /// assert!(!board.is_pseudo_legal(Move::new(Square::E2, Square::E4, Some(Piece::Queen))));
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn is_pseudo_legal(&self, mov: Move) -> bool {
+        MoveGen::<All>::new(self, &[]).contains(mov)
+    }
+
+    /// Get if a move is legal, which is exactly when [`Self::legal_moves`] would generate it. This
+    /// never panics, even if the move is not valid in this position.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/4r3/4K3 w - -").unwrap();
+    ///
+    /// assert!(board.is_pseudo_legal(Move::new(Square::E1, Square::F2, None)));
+    /// assert!(!board.is_legal(Move::new(Square::E1, Square::F2, None)));
+    /// assert!(board.is_legal(Move::new(Square::E1, Square::E2, None)));
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn is_legal(&self, mov: Move) -> bool {
+        MoveGen::<All, true>::new(self, &[]).contains(mov)
+    }
+
+    /// Append all legal moves to a [`MoveList`]. A list that is initially empty will never
+    /// overflow.
+    ///
+    /// # Panics
+    /// Panics if the list overflows.
+    #[inline(always)]
+    pub fn generate_into(&self, list: &mut MoveList) {
+        list.extend(self.legal_moves(&[]));
+    }
+}
+
+impl<'a, K: MoveKind, const LEGAL: bool> MoveGen<'a, K, LEGAL> {
+    #[inline(always)]
+    fn new(board: &'a Board, priority: &'a [Move]) -> Self {
+        let mut gen = Self {
+            board,
+            kind: PhantomData,
             priority,
             priority_at: 0,
+            emitted_from: Bitboard::default(),
+            emitted_promotions: 0,
 
             cur_piece_targets: Bitboard::default(),
             cur_piece_sq: Square::default(),
             cur_promote_to: 0,
 
-            pieces: self.our_pieces().into_iter(),
+            pieces: board.our_pieces().into_iter(),
+
+            check_mask: !Bitboard::default(),
+            pinned: Bitboard::default(),
+            king_danger: Bitboard::default(),
+
+            check_squares: [Bitboard::default(); 6],
+            discoverers: Bitboard::default(),
+        };
+
+        let us = board.side_to_move();
+        let ksq = board.our_king();
+        let checkers = board.checkers(us);
+
+        // evasion stage: when in check, non-king pieces can only capture the checker or interpose
+        // between it and the king, and only the king can move under a double check
+        match checkers.popcnt() {
+            0 => {},
+            1 => {
+                // SAFETY: there is exactly 1 checker
+                let checker = unsafe { checkers.first_square().unwrap_unchecked() };
+                gen.check_mask = Bitboard::between(ksq, checker) | checker.into();
+            },
+            _ => {
+                gen.check_mask = Bitboard::default();
+                gen.pieces = Bitboard::from(ksq).into_iter();
+            },
+        }
+
+        if LEGAL {
+            gen.pinned = board.pinned(us);
+            gen.king_danger = board.attacked_squares(!us, board.combined() ^ ksq.into());
+        }
+
+        if K::KIND == QUIET_CHECKS {
+            let their_king = board.their_king();
+            let bishop = bishop::moves(their_king, board.combined());
+            let rook = rook::moves(their_king, board.combined());
+
+            gen.check_squares = [
+                pawn::captures(!us, their_king),
+                knight::moves(their_king),
+                bishop,
+                rook,
+                bishop | rook,
+                Bitboard::default(),
+            ];
+            gen.discoverers = board.discovered_check_candidates();
         }
+
+        gen
     }
-}
 
-impl<const CAPTURES: bool> MoveGen<'_, CAPTURES> {
+    /// Get the targets of a piece, masked to only the kind of moves specified by `K`, and the
+    /// legal ones if `LEGAL`.
     #[inline(always)]
-    fn try_next(&mut self) -> Option<Result<Move, ()>> {
-        let target_mask = if CAPTURES { self.board.combined() } else { !Bitboard::default() };
+    fn targets(&self, piece: Piece, sq: Square) -> Bitboard {
+        let board = self.board;
+        let captures = board.their_pieces()
+            | if piece == Piece::Pawn { board.ep_square(board.side_to_move()) } else { Bitboard::default() };
+        let target_mask = match K::KIND {
+            CAPTURES => captures,
+            QUIETS | QUIET_CHECKS => !captures,
+            _ => !Bitboard::default(),
+        };
+
+        let targets = if LEGAL { self.legal_targets(piece, sq) } else { self.pseudo_legal_targets(piece, sq) } & target_mask;
+
+        if K::KIND == QUIET_CHECKS { self.checking_targets(piece, sq, targets) } else { targets }
+    }
+
+    #[inline(always)]
+    fn pseudo_legal_targets(&self, piece: Piece, sq: Square) -> Bitboard {
+        let board = self.board;
+        let us = board.side_to_move();
+        let targets = board.piece_targets::<false>(us, piece, sq);
+
+        if piece == Piece::King { return targets }
+
+        let ep = if piece == Piece::Pawn { targets & board.ep_square(us) } else { Bitboard::default() };
+
+        // en passant can evade a check by capturing the pawn that just moved, even though the
+        // pawn is not on the target square
+        let ep = ep.first_square().map_or(ep, |to| {
+            let captured = Square::new(to.file(), sq.rank());
+
+            if (self.check_mask & captured.into()).is_empty() { Bitboard::default() } else { ep }
+        });
+
+        (targets & self.check_mask) | ep
+    }
+
+    #[inline(always)]
+    fn legal_targets(&self, piece: Piece, sq: Square) -> Bitboard {
+        let board = self.board;
+        let us = board.side_to_move();
 
+        if piece == Piece::King {
+            let moves = king::moves(sq) & !board.our_pieces() & !self.king_danger;
+            let castles = if K::KIND == CAPTURES { Bitboard::default() } else { board.castle_targets(us, sq, self.king_danger) };
+
+            return moves | castles;
+        }
+
+        let mut targets = board.piece_targets::<false>(us, piece, sq);
+
+        // pinned pieces can only move along the line between the king and the pinner
+        if !(self.pinned & sq.into()).is_empty() {
+            targets &= Bitboard::line(board.our_king(), sq);
+        }
+
+        let ep = if piece == Piece::Pawn { targets & board.ep_square(us) } else { Bitboard::default() };
+        let legal_ep = if ep.is_empty() || self.is_legal_ep(sq) { ep } else { Bitboard::default() };
+
+        (targets & !ep & self.check_mask) | legal_ep
+    }
+
+    /// Get the targets that may give check. Promotions and castling are only candidates, and are
+    /// checked by [`Board::gives_check`] when generated.
+    #[inline(always)]
+    fn checking_targets(&self, piece: Piece, sq: Square, targets: Bitboard) -> Bitboard {
+        let mut checks = targets & self.check_squares[piece as usize];
+
+        match piece {
+            Piece::Pawn => checks |= targets & pawn::PROMOTION_SQUARES,
+            Piece::King => checks |= targets & (!king::moves(sq) | self.board.our_pieces()),
+            _ => {},
+        }
+
+        if !(self.discoverers & sq.into()).is_empty() {
+            checks |= targets & !Bitboard::line(self.board.their_king(), sq);
+        }
+
+        checks
+    }
+
+    /// Get if en passant capturing with a pawn on the given square leaves the king safe.
+    fn is_legal_ep(&self, from: Square) -> bool {
+        let board = self.board;
+        let us = board.side_to_move();
+        let ksq = board.our_king();
+
+        // SAFETY: only called when there is an en passant square
+        let to = unsafe { board.ep_square(us).first_square().unwrap_unchecked() };
+        let captured = Square::new(to.file(), from.rank());
+        let occupied = board.combined() ^ Bitboard::from(from) ^ captured.into() ^ to.into();
+
+        let their_rooks = board.rooks_of(!us) | board.queens_of(!us);
+        let their_bishops = board.bishops_of(!us) | board.queens_of(!us);
+        let other_checkers = board.checkers(us) & !Bitboard::from(captured)
+            & !board.rooks() & !board.bishops() & !board.queens();
+
+        (rook::moves(ksq, occupied) & their_rooks).is_empty()
+            && (bishop::moves(ksq, occupied) & their_bishops).is_empty()
+            && other_checkers.is_empty()
+    }
+
+    /// Get if the move is one of the moves this generator generates, ignoring the priority list.
+    fn contains(&self, mov: Move) -> bool {
+        let board = self.board;
+        let Some((piece, color)) = board.piece_and_color_on(mov.from()) else { return false };
+
+        if color != board.side_to_move() { return false }
+        if (self.targets(piece, mov.from()) & mov.to().into()).is_empty() { return false }
+
+        let promotes = piece == Piece::Pawn && !(pawn::PROMOTION_SQUARES & mov.to().into()).is_empty();
+        let valid_promotion = mov.promotion()
+            .map_or(!promotes, |promotion| promotes && Piece::PROMOTE_TO.contains(&promotion));
+
+        valid_promotion && (K::KIND != QUIET_CHECKS || board.gives_check(mov))
+    }
+
+    #[inline(always)]
+    fn try_next(&mut self) -> Option<Result<Move, ()>> {
         if self.priority_at < self.priority.len() {
             let candidate = self.priority[self.priority_at];
             self.priority_at += 1;
 
-            if self.priority[..self.priority_at - 1].contains(&candidate) { return Some(Err(())) }
-
-            return if let Some((piece, color)) = self.board.piece_and_color_on(candidate.from()) {
-                if color != self.board.side_to_move { return Some(Err(())) }
+            if self.priority[..self.priority_at - 1].contains(&candidate) || !self.contains(candidate) {
+                return Some(Err(()));
+            }
 
-                let targets = self.board.piece_targets::<false>(self.board.side_to_move(), piece, candidate.from())
-                    & target_mask;
+            match candidate.promotion() {
+                Some(promotion) => self.emitted_promotions |= 1 << promotion_index(candidate, promotion),
+                None => self.emitted_from |= candidate.from().into(),
+            }
 
-                Some((!(targets & candidate.to().into()).is_empty()).then_some(candidate).ok_or(()))
-            } else {
-                Some(Err(()))
-            };
+            return Some(Ok(candidate));
         }
 
         while self.cur_piece_targets.is_empty() {
             let square = self.pieces.next()?;
             let piece = self.board.piece_on(square).unwrap();
 
-            let piece_targets = self.board.piece_targets::<false>(self.board.side_to_move(), piece, square)
-                & target_mask;
-
-            self.cur_piece_targets = piece_targets;
+            self.cur_piece_targets = self.targets(piece, square);
             self.cur_piece_sq = square;
+
+            // remove the targets of the priority moves of this piece once, instead of checking
+            // every generated move against them
+            if !(self.emitted_from & square.into()).is_empty() {
+                for m in &self.priority[..self.priority_at] {
+                    if m.from() == square && m.promotion().is_none() {
+                        self.cur_piece_targets &= !Bitboard::from(m.to());
+                    }
+                }
+            }
         }
 
         if self.cur_promote_to == 0 {
@@ -134,11 +498,26 @@ impl<const CAPTURES: bool> MoveGen<'_, CAPTURES> {
             }
         };
 
-        Some((!self.priority.contains(&mov)).then_some(mov).ok_or(()))
+        if K::KIND == QUIET_CHECKS && !self.board.gives_check(mov) { return Some(Err(())) }
+
+        let emitted = mov.promotion()
+            .is_some_and(|promotion| self.emitted_promotions & (1 << promotion_index(mov, promotion)) != 0);
+
+        Some((!emitted).then_some(mov).ok_or(()))
     }
 }
 
-impl<const CAPTURES: bool> Iterator for MoveGen<'_, CAPTURES> {
+/// Get a unique index below 96 of a promotion, which is from one of 8 files, to the same or an
+/// adjacent file, and to one of 4 pieces.
+#[inline(always)]
+const fn promotion_index(mov: Move, promotion: Piece) -> u32 {
+    let from = mov.from().file() as u32;
+    let to = mov.to().file() as u32;
+
+    (from * 3 + to + 1 - from) * 4 + promotion as u32 - 1
+}
+
+impl<K: MoveKind, const LEGAL: bool> Iterator for MoveGen<'_, K, LEGAL> {
     type Item = Move;
 
     fn next(&mut self) -> Option<Move> {
@@ -150,4 +529,4 @@ impl<const CAPTURES: bool> Iterator for MoveGen<'_, CAPTURES> {
     }
 }
 
-impl<const CAPTURES: bool> core::iter::FusedIterator for MoveGen<'_, CAPTURES> {}
+impl<K: MoveKind, const LEGAL: bool> core::iter::FusedIterator for MoveGen<'_, K, LEGAL> {}
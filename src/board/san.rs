@@ -0,0 +1,203 @@
+use core::fmt;
+
+use super::{Board, File, Move, Piece, Rank, Square};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanError {
+    InvalidSyntax,
+    IllegalMove,
+    AmbiguousMove,
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax => write!(f, "invalid SAN move syntax"),
+            Self::IllegalMove => write!(f, "no legal move matches the SAN move"),
+            Self::AmbiguousMove => write!(f, "more than 1 legal move matches the SAN move"),
+        }
+    }
+}
+
+impl core::error::Error for SanError {}
+
+/// A helper struct to format a legal move in Standard Algebraic Notation.
+///
+/// This is created by [`Board::san`].
+#[derive(Debug, Clone, Copy)]
+pub struct SanDisplay<'a> {
+    board: &'a Board,
+    mov: Move,
+}
+
+impl Board {
+    /// Get a helper struct to format a legal move in Standard Algebraic Notation, with
+    /// disambiguation, captures, promotions, castling and check or checkmate suffixes. The output
+    /// is unspecified if the move is not legal.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "6k1/P4ppp/8/8/8/8/8/R3K1NR w KQ -").unwrap();
+    ///
+    /// assert_eq!(board.san(Move::new(Square::A7, Square::A8, Some(Piece::Queen))).to_string(), "a8=Q#");
+    /// assert_eq!(board.san(Move::new(Square::E1, Square::C1, None)).to_string(), "O-O-O");
+    /// assert_eq!(board.san(Move::new(Square::G1, Square::F3, None)).to_string(), "Nf3");
+    /// assert_eq!(board.san(Move::new(Square::A1, Square::A6, None)).to_string(), "Ra6");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn san(&self, mov: Move) -> SanDisplay<'_> {
+        SanDisplay { board: self, mov }
+    }
+
+    /// Parse a move in Standard Algebraic Notation and find the legal move it refers to.
+    ///
+    /// Common variants are accepted, like `0-0` for castling, missing or extra check suffixes and
+    /// annotations, promotions without `=` like `e8Q`, and redundant disambiguation like `Ng1f3`.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::default();
+    ///
+    /// assert_eq!(board.parse_san("Nf3"), Ok(Move::new(Square::G1, Square::F3, None)));
+    /// assert_eq!(board.parse_san("e4!?"), Ok(Move::new(Square::E2, Square::E4, None)));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the string is malformed, or does not refer to exactly 1 legal move.
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+
+        match san {
+            "O-O" | "0-0" => return self.find_castle(true),
+            "O-O-O" | "0-0-0" => return self.find_castle(false),
+            _ => {},
+        }
+
+        let (san, promotion) = match san.as_bytes() {
+            [.., b'1'..=b'8', b'=' | b'(', p] | [.., b'1'..=b'8', p] if promotion_piece(*p).is_some() => {
+                (san[..san.len() - 1].trim_end_matches(['=', '(']), promotion_piece(*p))
+            },
+            [.., b'1'..=b'8', b'(' | b'=', p, b')'] if promotion_piece(*p).is_some() => {
+                (&san[..san.len() - 3], promotion_piece(*p))
+            },
+            _ => (san, None),
+        };
+
+        let (piece, san) = match san.as_bytes().first() {
+            Some(b'N') => (Piece::Knight, &san[1..]),
+            Some(b'B') => (Piece::Bishop, &san[1..]),
+            Some(b'R') => (Piece::Rook, &san[1..]),
+            Some(b'Q') => (Piece::Queen, &san[1..]),
+            Some(b'K') => (Piece::King, &san[1..]),
+            Some(b'P') => (Piece::Pawn, &san[1..]),
+            _ => (Piece::Pawn, san),
+        };
+
+        let [disambiguation @ .., file @ b'a'..=b'h', rank @ b'1'..=b'8'] = san.as_bytes() else {
+            return Err(SanError::InvalidSyntax);
+        };
+        let to = Square::new(File::ALL[(file - b'a') as usize], Rank::ALL[(rank - b'1') as usize]);
+
+        let mut from_file = None;
+        let mut from_rank = None;
+        for ch in disambiguation {
+            match ch {
+                b'a'..=b'h' => from_file = Some(File::ALL[(ch - b'a') as usize]),
+                b'1'..=b'8' => from_rank = Some(Rank::ALL[(ch - b'1') as usize]),
+                b'x' | b':' | b'-' => {},
+                _ => return Err(SanError::InvalidSyntax),
+            }
+        }
+
+        let mut found = None;
+        for m in self.legal_moves(&[]) {
+            if m.to() != to
+                || self.piece_on(m.from()) != Some(piece)
+                || m.promotion() != promotion
+                || from_file.is_some_and(|f| f != m.from().file())
+                || from_rank.is_some_and(|r| r != m.from().rank())
+                || self.is_castling(m)
+            {
+                continue;
+            }
+
+            if found.replace(m).is_some() { return Err(SanError::AmbiguousMove) }
+        }
+
+        found.ok_or(SanError::IllegalMove)
+    }
+
+    fn find_castle(&self, king_side: bool) -> Result<Move, SanError> {
+        let ksq = self.our_king();
+
+        self.legal_moves(&[])
+            .find(|m| m.from() == ksq && self.is_castling(*m) && (m.to().file() > ksq.file()) == king_side)
+            .ok_or(SanError::IllegalMove)
+    }
+
+    #[inline(always)]
+    fn is_castling(&self, mov: Move) -> bool {
+        self.piece_on(mov.from()) == Some(Piece::King) && self.castling_rook(self.side_to_move(), mov).is_some()
+    }
+}
+
+const fn promotion_piece(ch: u8) -> Option<Piece> {
+    match ch {
+        b'N' | b'n' => Some(Piece::Knight),
+        b'B' | b'b' => Some(Piece::Bishop),
+        b'R' | b'r' => Some(Piece::Rook),
+        b'Q' | b'q' => Some(Piece::Queen),
+        _ => None,
+    }
+}
+
+impl fmt::Display for SanDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { board, mov } = *self;
+        let (from, to) = (mov.from(), mov.to());
+        let Some(piece) = board.piece_on(from) else { return write!(f, "{mov}") };
+
+        if board.is_castling(mov) {
+            write!(f, "{}", if to.file() > from.file() { "O-O" } else { "O-O-O" })?;
+        } else {
+            let capture = board.color_on(to).is_some() || (piece == Piece::Pawn && from.file() != to.file());
+
+            if piece == Piece::Pawn {
+                if capture { write!(f, "{}", from.file())? }
+            } else {
+                write!(f, "{}", piece.to_uppercase_char())?;
+
+                let (mut ambiguous, mut same_file, mut same_rank) = (false, false, false);
+                for m in board.legal_moves(&[]) {
+                    if m.to() != to || m.from() == from || board.piece_on(m.from()) != Some(piece) { continue }
+
+                    ambiguous = true;
+                    same_file |= m.from().file() == from.file();
+                    same_rank |= m.from().rank() == from.rank();
+                }
+
+                if ambiguous && (!same_file || same_rank) { write!(f, "{}", from.file())? }
+                if ambiguous && same_file { write!(f, "{}", from.rank())? }
+            }
+
+            if capture { write!(f, "x")? }
+            write!(f, "{to}")?;
+
+            if let Some(promotion) = mov.promotion() {
+                write!(f, "={}", promotion.to_uppercase_char())?;
+            }
+        }
+
+        if board.gives_check(mov) {
+            let mate = board.copy_make_move(mov).legal_moves(&[]).next().is_none();
+            write!(f, "{}", if mate { '#' } else { '+' })?;
+        }
+
+        Ok(())
+    }
+}
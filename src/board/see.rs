@@ -0,0 +1,207 @@
+use super::{Bitboard, Board, Color, Move, Piece, Square, bishop, pawn, rook};
+
+/// The piece values used by static exchange evaluation, indexed by [`Piece`].
+pub const SEE_VALUES: [i32; 6] = [100, 300, 300, 500, 900, 0];
+
+impl Board {
+    /// Get the static exchange evaluation of a move, which is the material the side to move gains
+    /// after the sequence of captures on the destination square where both sides capture with
+    /// their least valuable piece and may stop capturing at any time.
+    ///
+    /// X-ray attackers behind sliders, promotions and en passant are handled, but pins are not.
+    /// Castling is always evaluated as 0.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// // the rook is defended by a pawn
+    /// let board = Board::from_epd(false, "4k3/8/2p5/3r4/8/8/3R4/4K3 w - -").unwrap();
+    /// assert_eq!(board.see(Move::new(Square::D2, Square::D5, None)), 0);
+    ///
+    /// // the pawn is defended by the queen through the rook
+    /// let board = Board::from_epd(false, "3qk3/3r4/8/3p4/8/8/3R4/3QK3 w - -").unwrap();
+    /// assert_eq!(board.see(Move::new(Square::D2, Square::D5, None)), -400);
+    /// ```
+    #[must_use]
+    pub fn see(&self, mov: Move) -> i32 {
+        let to = mov.to();
+        let Some((first_gain, mut on_square, us, mut occupied)) = self.see_start(mov) else { return 0 };
+
+        let mut gain = [0; 32];
+        let mut depth = 0;
+        let mut side = us;
+
+        gain[0] = first_gain;
+
+        let diagonal = self.bishops() | self.queens();
+        let orthogonal = self.rooks() | self.queens();
+        let mut attackers = self.attackers_with(to, occupied) & occupied;
+
+        loop {
+            side = !side;
+            attackers &= occupied;
+
+            let ours = attackers & self.color_combined(side);
+            let Some(attacker) = self.least_valuable(ours) else { break };
+
+            // the king can't capture into a defended square
+            if attacker == Piece::King && !(attackers & self.color_combined(!side)).is_empty() { break }
+
+            depth += 1;
+            gain[depth] = SEE_VALUES[on_square as usize] - gain[depth - 1];
+            on_square = attacker;
+
+            if attacker == Piece::Pawn && !(pawn::PROMOTION_SQUARES & to.into()).is_empty() {
+                gain[depth] += SEE_VALUES[Piece::Queen as usize] - SEE_VALUES[Piece::Pawn as usize];
+                on_square = Piece::Queen;
+            }
+
+            // SAFETY: `least_valuable` found a piece of this type
+            let sq = unsafe { (ours & self.piece_combined(attacker)).first_square().unwrap_unchecked() };
+            occupied ^= sq.into();
+
+            // reveal x-ray attackers behind the piece that just captured
+            if matches!(attacker, Piece::Pawn | Piece::Bishop | Piece::Queen) {
+                attackers |= bishop::moves(to, occupied) & diagonal;
+            }
+            if matches!(attacker, Piece::Rook | Piece::Queen) {
+                attackers |= rook::moves(to, occupied) & orthogonal;
+            }
+
+            if depth == gain.len() - 1 { break }
+        }
+
+        while depth > 0 {
+            depth -= 1;
+            gain[depth] = -(-gain[depth]).max(gain[depth + 1]);
+        }
+
+        gain[0]
+    }
+
+    /// Get if the static exchange evaluation of a move is at least `threshold`.
+    ///
+    /// Unlike comparing [`Self::see`] with the threshold, the exchange is only followed until its
+    /// result is known to be on one side of the threshold, which makes this cheaper for pruning.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/2p5/3r4/8/8/3R4/4K3 w - -").unwrap();
+    /// let mov = Move::new(Square::D2, Square::D5, None);
+    ///
+    /// assert!(board.see_ge(mov, 0));
+    /// assert!(!board.see_ge(mov, 1));
+    /// ```
+    #[must_use]
+    pub fn see_ge(&self, mov: Move, threshold: i32) -> bool {
+        let to = mov.to();
+        let Some((first_gain, on_square, us, mut occupied)) = self.see_start(mov) else { return threshold <= 0 };
+
+        let diagonal = self.bishops() | self.queens();
+        let orthogonal = self.rooks() | self.queens();
+        let mut attackers = self.attackers_with(to, occupied) & occupied;
+
+        // only the pawns of one color can capture onto a promotion square, and they also gain the
+        // promotion when they do
+        let promoting_pawns = if (pawn::PROMOTION_SQUARES & to.into()).is_empty() {
+            Bitboard::default()
+        } else {
+            attackers & self.pawns()
+        };
+        let promotion_gain = |side: Color, occupied: Bitboard| {
+            if (promoting_pawns & self.color_combined(side) & occupied).is_empty() {
+                0
+            } else {
+                SEE_VALUES[Piece::Queen as usize] - SEE_VALUES[Piece::Pawn as usize]
+            }
+        };
+
+        // the balance over the threshold if the exchange stops after this move
+        let mut swap = first_gain - threshold;
+        if swap < 0 { return false }
+
+        // the balance over the threshold for the opponent if they capture back and it stops there,
+        // which is settled if it is not positive
+        swap = SEE_VALUES[on_square as usize] - swap;
+        if swap + promotion_gain(!us, occupied) <= 0 { return true }
+
+        let mut side = us;
+        let mut result = true;
+
+        loop {
+            side = !side;
+            attackers &= occupied;
+
+            let ours = attackers & self.color_combined(side);
+            let Some(attacker) = self.least_valuable(ours) else { break };
+
+            // the result if this capture is the last one
+            result = !result;
+
+            // the king can't capture into a defended square
+            if attacker == Piece::King {
+                return if (attackers & self.color_combined(!side)).is_empty() { result } else { !result };
+            }
+
+            // a pawn promoting on capture gains as much as it then risks over the pawn, so the swap
+            // is the same as for any pawn
+            swap = SEE_VALUES[attacker as usize] - swap;
+
+            // SAFETY: `least_valuable` found a piece of this type
+            let sq = unsafe { (ours & self.piece_combined(attacker)).first_square().unwrap_unchecked() };
+            occupied ^= sq.into();
+
+            // the result is settled if capturing back doesn't pay off for the opponent
+            if swap + promotion_gain(!side, occupied) < i32::from(result) { break }
+
+            // reveal x-ray attackers behind the piece that just captured
+            if matches!(attacker, Piece::Pawn | Piece::Bishop | Piece::Queen) {
+                attackers |= bishop::moves(to, occupied) & diagonal;
+            }
+            if matches!(attacker, Piece::Rook | Piece::Queen) {
+                attackers |= rook::moves(to, occupied) & orthogonal;
+            }
+        }
+
+        result
+    }
+
+    /// Get the gain of the move itself, the piece it leaves on the destination square, the color
+    /// that moves and the occupancy after it, or `None` if the move is evaluated as 0.
+    #[inline(always)]
+    fn see_start(&self, mov: Move) -> Option<(i32, Piece, Color, Bitboard)> {
+        let (from, to) = (mov.from(), mov.to());
+        let (piece, us) = self.piece_and_color_on(from)?;
+
+        if piece == Piece::King
+            && (self.color_on(to) == Some(us) || (from.file() as u8).abs_diff(to.file() as u8) > 1)
+        {
+            return None;
+        }
+
+        let mut occupied = self.combined() ^ from.into();
+        let captured = match self.piece_and_color_on(to) {
+            Some((captured, color)) if color != us => SEE_VALUES[captured as usize],
+            Some(_) => return None,
+            None if piece == Piece::Pawn && from.file() != to.file() => {
+                occupied ^= Square::new(to.file(), from.rank()).into();
+                SEE_VALUES[Piece::Pawn as usize]
+            },
+            None => 0,
+        };
+
+        let on_square = mov.promotion().unwrap_or(piece);
+        let gain = captured + SEE_VALUES[on_square as usize] - SEE_VALUES[piece as usize];
+
+        Some((gain, on_square, us, occupied | to.into()))
+    }
+
+    /// Get the least valuable piece type among the given pieces.
+    #[inline(always)]
+    fn least_valuable(&self, pieces: Bitboard) -> Option<Piece> {
+        Piece::ALL.into_iter().find(|piece| !(pieces & self.piece_combined(*piece)).is_empty())
+    }
+}
@@ -0,0 +1,111 @@
+use core::fmt;
+
+use super::{Board, File, Move, Piece, Rank, Square, king};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UciMoveError {
+    InvalidLength,
+    InvalidSquare,
+    InvalidPromotion(char),
+    IllegalMove,
+}
+
+impl fmt::Display for UciMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "UCI move should be 4 or 5 characters long"),
+            Self::InvalidSquare => write!(f, "invalid square in UCI move"),
+            Self::InvalidPromotion(ch) => write!(f, "invalid promotion piece `{ch}`"),
+            Self::IllegalMove => write!(f, "move is not legal in the position"),
+        }
+    }
+}
+
+impl core::error::Error for UciMoveError {}
+
+impl Move {
+    /// Parse a move in UCI long algebraic notation, like `e2e4` or `e7e8q`, and check that it is
+    /// legal on the board.
+    ///
+    /// Castling is accepted in both the standard notation (`e1g1`) and the Chess960 notation where
+    /// the king captures its own rook (`e1h1`), and converted to the one used by the board
+    /// according to its `chess960` flag.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::{board::uci::UciMoveError, prelude::*};
+    /// #
+    /// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/4K2R w K -").unwrap();
+    /// let castle = Move::new(Square::E1, Square::G1, None);
+    ///
+    /// assert_eq!(Move::from_uci(&board, "e1g1"), Ok(castle));
+    /// assert_eq!(Move::from_uci(&board, "e1h1"), Ok(castle));
+    /// assert_eq!(Move::from_uci(&board, "e1e3"), Err(UciMoveError::IllegalMove));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the string is malformed or the move is illegal.
+    pub fn from_uci(board: &Board, uci: &str) -> Result<Self, UciMoveError> {
+        let mut chars = uci.chars();
+        let from = parse_square(chars.next(), chars.next())?;
+        let to = parse_square(chars.next(), chars.next())?;
+        let promotion = match chars.next() {
+            None => None,
+            Some('n' | 'N') => Some(Piece::Knight),
+            Some('b' | 'B') => Some(Piece::Bishop),
+            Some('r' | 'R') => Some(Piece::Rook),
+            Some('q' | 'Q') => Some(Piece::Queen),
+            Some(ch) => return Err(UciMoveError::InvalidPromotion(ch)),
+        };
+
+        if chars.next().is_some() { return Err(UciMoveError::InvalidLength) }
+        if from == to { return Err(UciMoveError::IllegalMove) }
+
+        let mov = convert_castling(board, Self::new(from, to, promotion));
+        board.is_legal(mov).then_some(mov).ok_or(UciMoveError::IllegalMove)
+    }
+}
+
+const fn parse_square(file: Option<char>, rank: Option<char>) -> Result<Square, UciMoveError> {
+    match (file, rank) {
+        (Some(file @ 'a'..='h'), Some(rank @ '1'..='8')) => Ok(Square::new(
+            File::ALL[(file as u8 - b'a') as usize],
+            Rank::ALL[(rank as u8 - b'1') as usize],
+        )),
+        (Some(_), Some(_)) => Err(UciMoveError::InvalidSquare),
+        _ => Err(UciMoveError::InvalidLength),
+    }
+}
+
+/// Convert a castling move in the notation not used by the board to the one used by the board.
+pub(crate) fn convert_castling(board: &Board, mov: Move) -> Move {
+    let (from, to) = (mov.from(), mov.to());
+    let us = board.side_to_move();
+    let back_rank = us.back_rank();
+
+    if board.piece_on(from) != Some(Piece::King) || from.rank() != back_rank || to.rank() != back_rank {
+        return mov;
+    }
+
+    let rights = board.castle_rights_of(us);
+    let chess960 = board.is_chess960();
+
+    for (allowed, rook_file) in [
+        (rights.king_side(), rights.king_side_file()),
+        (rights.queen_side(), rights.queen_side_file()),
+    ] {
+        if !allowed { continue }
+
+        let rook_sq = Square::new(rook_file, back_rank);
+        let (king_to, _) = king::castle_to(us, from.file(), rook_file);
+
+        if chess960 && to == king_to && (from.file() as u8).abs_diff(to.file() as u8) > 1 {
+            return Move::new(from, rook_sq, None);
+        }
+        if !chess960 && to == rook_sq && board.piece_and_color_on(to) == Some((Piece::Rook, us)) {
+            return Move::new(from, king_to, None);
+        }
+    }
+
+    mov
+}
@@ -13,8 +13,14 @@ impl Board {
             castle_rights: [CastleRights::default_no_rights(); 2],
 
             hash: 0,
+            pawn_hash: 0,
+            material_hash: 0,
+            non_pawn_hashes: [0; 2],
             en_passant: None,
 
+            halfmove_clock: 0,
+            fullmove_number: 1,
+
             chess960: false,
         }
     }
@@ -24,6 +30,16 @@ impl Board {
     #[must_use]
     pub const fn side_to_move(&self) -> Color { self.side_to_move }
 
+    /// Get the number of plies since the last capture or pawn move.
+    #[inline(always)]
+    #[must_use]
+    pub const fn halfmove_clock(&self) -> u16 { self.halfmove_clock }
+
+    /// Get the full move number. It starts at 1 and is incremented after black moves.
+    #[inline(always)]
+    #[must_use]
+    pub const fn fullmove_number(&self) -> u16 { self.fullmove_number }
+
     /// Get the piece and color on a given square.
     #[inline(always)]
     #[must_use]
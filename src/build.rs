@@ -24,3 +24,10 @@ mod bb_data {
     pub const LEFTS: [Bitboard; 8] = [Bitboard(0); 8];
     pub const RIGHTS: [Bitboard; 8] = [Bitboard(0); 8];
 }
+
+mod line {
+    use crate::bitboard::Bitboard;
+
+    pub static BETWEEN: [[Bitboard; 64]; 64] = [[Bitboard(0); 64]; 64];
+    pub static LINE: [[Bitboard; 64]; 64] = [[Bitboard(0); 64]; 64];
+}
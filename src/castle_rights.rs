@@ -19,14 +19,12 @@ impl CastleRights {
         Self(0b11100000)
     }
 
-    #[allow(unused)]
     #[inline(always)]
     pub(crate) const fn set_ks_file(&mut self, f: File) {
         self.0 &= !0b11100000;
         self.0 |= (f as u8) << 5;
     }
 
-    #[allow(unused)]
     #[inline(always)]
     pub(crate) const fn set_qs_file(&mut self, f: File) {
         self.0 &= !0b00011100;
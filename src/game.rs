@@ -0,0 +1,309 @@
+use core::{fmt, mem::MaybeUninit};
+
+use crate::{bitboard::Bitboard, board::{cuckoo, Board, MoveRestorer}, chess_move::Move, color::Color};
+
+/// The way a game ended, or can be ended by claiming a draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Checkmate { winner: Color },
+    Stalemate,
+    /// Neither side can checkmate by any sequence of legal moves, see
+    /// [`Board::is_insufficient_material`].
+    DeadPosition,
+    /// The same position occurred 5 times.
+    FivefoldRepetition,
+    /// 75 moves by each side without a capture or pawn move.
+    SeventyFiveMoveRule,
+    /// The same position occurred 3 times, which can be claimed as a draw.
+    ThreefoldRepetition,
+    /// 50 moves by each side without a capture or pawn move, which can be claimed as a draw.
+    FiftyMoveRule,
+}
+
+impl Outcome {
+    /// Get the winner, or `None` if this is a draw.
+    #[inline(always)]
+    #[must_use]
+    pub const fn winner(self) -> Option<Color> {
+        match self {
+            Self::Checkmate { winner } => Some(winner),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_draw(self) -> bool {
+        self.winner().is_none()
+    }
+}
+
+/// The status of a game, see [`Game::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameStatus {
+    Ongoing,
+    /// The game is not over, but either player can claim a draw.
+    DrawClaimable(Outcome),
+    /// The game is over, either by checkmate or by an automatic draw.
+    Over(Outcome),
+}
+
+/// A board along with the history of the moves made on it, stored on the stack with a fixed
+/// capacity of `N` plies, so that repetitions can be detected without an allocator.
+///
+/// # Example
+/// ```
+/// # use dychess::prelude::*;
+/// #
+/// let mut game = Game::<64>::default();
+///
+/// for _ in 0..2 {
+///     game.push(Move::new(Square::G1, Square::F3, None));
+///     game.push(Move::new(Square::G8, Square::F6, None));
+///     game.push(Move::new(Square::F3, Square::G1, None));
+///     game.push(Move::new(Square::F6, Square::G8, None));
+/// }
+///
+/// assert_eq!(game.len(), 8);
+/// assert!(game.is_threefold());
+///
+/// game.pop();
+/// assert!(!game.is_threefold());
+/// assert!(game.is_repetition(2));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Game<const N: usize = 1024> {
+    board: Board,
+    /// The hashes of the positions before each move.
+    hashes: [u64; N],
+    moves: [MaybeUninit<(Move, MoveRestorer)>; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for Game<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(Board::default())
+    }
+}
+
+impl<const N: usize> Game<N> {
+    /// The maximum number of plies the history can hold.
+    pub const CAPACITY: usize = N;
+
+    /// Create a game without history starting from the given position.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(board: Board) -> Self {
+        Self {
+            board,
+            hashes: [0; N],
+            moves: [MaybeUninit::uninit(); N],
+            len: 0,
+        }
+    }
+
+    /// Get the current position.
+    #[inline(always)]
+    #[must_use]
+    pub const fn board(&self) -> &Board { &self.board }
+
+    /// Get the number of moves in the history.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize { self.len }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_full(&self) -> bool { self.len == N }
+
+    /// Get the hashes of the positions before each move in the history, see [`Board::get_hash`].
+    #[inline(always)]
+    #[must_use]
+    pub fn hashes(&self) -> &[u64] { &self.hashes[..self.len] }
+
+    /// Get an iterator of the moves in the history.
+    #[inline(always)]
+    #[must_use]
+    pub fn moves(&self) -> impl DoubleEndedIterator<Item = Move> + ExactSizeIterator + '_ {
+        // SAFETY: moves before `len` are initialized
+        self.moves[..self.len].iter().map(|m| unsafe { m.assume_init() }.0)
+    }
+
+    /// Make a move on the board and add it to the history.
+    ///
+    /// # Panics
+    /// Panics if the history is already full, or see [`Board::make_move`].
+    #[inline(always)]
+    pub fn push(&mut self, mov: Move) {
+        assert!(self.try_push(mov).is_ok(), "game history is full");
+    }
+
+    /// Make a move on the board and add it to the history, or give it back if the history is
+    /// already full.
+    ///
+    /// # Errors
+    /// Returns the move if the history is already full.
+    ///
+    /// # Panics
+    /// See [`Board::make_move`].
+    #[inline(always)]
+    pub fn try_push(&mut self, mov: Move) -> Result<(), Move> {
+        if self.len == N { return Err(mov) }
+
+        self.hashes[self.len] = self.board.get_hash();
+        self.moves[self.len] = MaybeUninit::new((mov, self.board.make_move(mov)));
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Undo the last move and return it.
+    #[inline(always)]
+    pub fn pop(&mut self) -> Option<Move> {
+        if self.len == 0 { return None }
+
+        self.len -= 1;
+        // SAFETY: moves before `len` are initialized
+        let (mov, restorer) = unsafe { self.moves[self.len].assume_init() };
+        self.board.unmake_move(mov, restorer);
+        Some(mov)
+    }
+
+    /// Get if the current position has occurred at least `n` times, including the current one.
+    ///
+    /// Only the positions since the last capture or pawn move are scanned, since earlier positions
+    /// can't be repeated. Positions before the start of the history are unknown and not counted.
+    #[must_use]
+    pub fn is_repetition(&self, n: usize) -> bool {
+        let hash = self.board.get_hash();
+        let window = (self.board.halfmove_clock() as usize).min(self.len);
+
+        // positions with the same side to move are 2, 4, 6, ... plies ago
+        let count = self.hashes[self.len - window..self.len]
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .filter(|h| **h == hash)
+            .count();
+
+        count + 1 >= n
+    }
+
+    /// Get if the current position has occurred at least 3 times, which allows a draw to be
+    /// claimed.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_threefold(&self) -> bool {
+        self.is_repetition(3)
+    }
+
+    /// Get if the side to move has a reversible move that reaches a position from the history,
+    /// using cuckoo hashing over the piece moves so that no moves are generated. This lets a
+    /// search score a draw one ply before the repetition actually occurs.
+    ///
+    /// `ply` is the distance from the root of the search. Repetitions within the last `ply` plies
+    /// are always detected, while repetitions of positions at or before the root are only
+    /// detected if that position has already occurred twice.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let mut game = Game::<16>::default();
+    /// game.push(Move::new(Square::G1, Square::F3, None));
+    /// game.push(Move::new(Square::G8, Square::F6, None));
+    /// game.push(Move::new(Square::F3, Square::G1, None));
+    ///
+    /// // Nf6-g8 repeats the starting position
+    /// assert!(game.has_upcoming_repetition(4));
+    /// assert!(!game.has_upcoming_repetition(0));
+    /// ```
+    #[must_use]
+    pub fn has_upcoming_repetition(&self, ply: usize) -> bool {
+        let board = &self.board;
+        let window = (board.halfmove_clock() as usize).min(self.len);
+        if window < 3 { return false }
+
+        let hash = board.get_hash();
+        let start = self.len - window;
+
+        for i in (3..=window).step_by(2) {
+            let idx = self.len - i;
+            let Some(mov) = cuckoo::lookup(hash ^ self.hashes[idx]) else { continue };
+            let (a, b) = (mov.from(), mov.to());
+
+            if !(Bitboard::between(a, b) & board.combined()).is_empty() { continue }
+            if ply > i { return true }
+
+            // both directions of a move share an entry, so check that the piece to move back is
+            // ours rather than the opponent's
+            let sq = if board.color_on(a).is_some() { a } else { b };
+            if board.color_on(sq) != Some(board.side_to_move()) { continue }
+
+            if self.hashes[start..idx].iter().rev().skip(1).step_by(2).any(|h| *h == self.hashes[idx]) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Classify the current position by the rules of chess.
+    ///
+    /// Checkmate takes precedence over the draw rules, so a checkmate on the 75th move is still a
+    /// win. Threefold repetition and the 50-move rule only make a draw claimable.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::prelude::*;
+    /// #
+    /// let mut game = Game::<16>::default();
+    /// assert_eq!(game.status(), GameStatus::Ongoing);
+    ///
+    /// game.push(Move::new(Square::F2, Square::F3, None));
+    /// game.push(Move::new(Square::E7, Square::E5, None));
+    /// game.push(Move::new(Square::G2, Square::G4, None));
+    /// game.push(Move::new(Square::D8, Square::H4, None));
+    /// assert_eq!(game.status(), GameStatus::Over(Outcome::Checkmate { winner: Color::Black }));
+    /// ```
+    #[must_use]
+    pub fn status(&self) -> GameStatus {
+        let board = &self.board;
+
+        if board.legal_moves(&[]).next().is_none() {
+            return GameStatus::Over(if board.is_check() {
+                Outcome::Checkmate { winner: !board.side_to_move() }
+            } else {
+                Outcome::Stalemate
+            });
+        }
+
+        if board.is_insufficient_material() {
+            GameStatus::Over(Outcome::DeadPosition)
+        } else if self.is_repetition(5) {
+            GameStatus::Over(Outcome::FivefoldRepetition)
+        } else if board.halfmove_clock() >= 150 {
+            GameStatus::Over(Outcome::SeventyFiveMoveRule)
+        } else if self.is_threefold() {
+            GameStatus::DrawClaimable(Outcome::ThreefoldRepetition)
+        } else if board.halfmove_clock() >= 100 {
+            GameStatus::DrawClaimable(Outcome::FiftyMoveRule)
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
+
+impl<const N: usize> fmt::Debug for Game<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Game")
+            .field("board", &self.board)
+            .field("hashes", &self.hashes())
+            .finish_non_exhaustive()
+    }
+}
@@ -27,9 +27,9 @@ pub fn generate_lefts(f: &mut impl Write) {
     write!(f, "pub const LEFTS: [Bitboard; 8] = [").unwrap();
 
     let mut acc = Bitboard::default();
-    for r in Rank::ALL {
+    for file in File::ALL {
         write!(f, "Bitboard({}),", acc.0).unwrap();
-        acc |= r.into();
+        acc |= file.into();
     }
 
     write!(f, "];").unwrap();
@@ -39,8 +39,8 @@ pub fn generate_rights(f: &mut impl Write) {
     write!(f, "pub const RIGHTS: [Bitboard; 8] = [").unwrap();
 
     let mut acc = !Bitboard::default();
-    for r in Rank::ALL {
-        acc ^= r.into();
+    for file in File::ALL {
+        acc ^= file.into();
         write!(f, "Bitboard({}),", acc.0).unwrap();
     }
 
@@ -29,25 +29,18 @@ fn generate_moves(f: &mut impl Write) {
 }
 
 fn generate_castle_paths(f: &mut impl Write) {
-    write!(f, "static CASTLE_PATH: [[[Bitboard; 2]; 8]; 2] = [").unwrap();
+    write!(f, "static CASTLE_PATH: [[[Bitboard; 8]; 8]; 2] = [").unwrap();
     for color in Color::ALL {
         write!(f, "[").unwrap();
         for king_file in File::ALL {
             write!(f, "[").unwrap();
-            for castle_side in [File::G, File::C] {
-                let mut bb = Bitboard::default();
-                if king_file > castle_side {
-                    // castle to left
-                    for f in File::ALL.into_iter().skip(2) {
-                        bb |= Square::new(f, color.back_rank()).into();
-                        if f == king_file { break };
-                    }
+            for rook_file in File::ALL {
+                let (king_to, _) = castle_to(king_file, rook_file);
+                let bb = if king_file == rook_file {
+                    Bitboard::default()
                 } else {
-                    // castle to right
-                    for f in File::ALL.into_iter().skip(king_file as usize) {
-                        bb |= Square::new(f, color.back_rank()).into();
-                        if f == castle_side { break };
-                    }
+                    // every square the king passes through, including where it starts and ends
+                    file_span(color, king_file, king_to)
                 };
 
                 write!(f, "Bitboard({}),", bb.0).unwrap();
@@ -58,25 +51,20 @@ fn generate_castle_paths(f: &mut impl Write) {
     }
     write!(f, "];").unwrap();
 
-    write!(f, "static CASTLE_CLEARANCE: [[[Bitboard; 2]; 8]; 2] = [").unwrap();
+    write!(f, "static CASTLE_CLEARANCE: [[[Bitboard; 8]; 8]; 2] = [").unwrap();
     for color in Color::ALL {
         write!(f, "[").unwrap();
         for king_file in File::ALL {
             write!(f, "[").unwrap();
-            for castle_side in [File::G, File::C] {
-                let mut bb = Bitboard::default();
-                if king_file > castle_side {
-                    // castle to left
-                    for f in File::ALL.into_iter().skip(1) {
-                        if f == king_file { break };
-                        bb |= Square::new(f, color.back_rank()).into();
-                    }
+            for rook_file in File::ALL {
+                let (king_to, rook_to) = castle_to(king_file, rook_file);
+                let bb = if king_file == rook_file {
+                    Bitboard::default()
                 } else {
-                    // castle to right
-                    for f in File::ALL.into_iter().skip(king_file as usize + 1) {
-                        bb |= Square::new(f, color.back_rank()).into();
-                        if f == castle_side { break };
-                    }
+                    // every square the king and the rook passes through, except for themselves
+                    (file_span(color, king_file, king_to) | file_span(color, rook_file, rook_to))
+                        & !Bitboard::from(Square::new(king_file, color.back_rank()))
+                        & !Bitboard::from(Square::new(rook_file, color.back_rank()))
                 };
 
                 write!(f, "Bitboard({}),", bb.0).unwrap();
@@ -88,6 +76,23 @@ fn generate_castle_paths(f: &mut impl Write) {
     write!(f, "];").unwrap();
 }
 
+/// Get the files the king and the rook ends up on after castling.
+fn castle_to(king_file: File, rook_file: File) -> (File, File) {
+    if king_file < rook_file {
+        (File::G, File::F)
+    } else {
+        (File::C, File::D)
+    }
+}
+
+fn file_span(color: Color, a: File, b: File) -> Bitboard {
+    let mut bb = Bitboard::default();
+    for f in &File::ALL[a.min(b) as usize..=a.max(b) as usize] {
+        bb |= Square::new(*f, color.back_rank()).into();
+    }
+    bb
+}
+
 fn generate_castle_moves(f: &mut impl Write) {
     write!(f, "
         #[allow(clippy::doc_markdown)]
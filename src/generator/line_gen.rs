@@ -0,0 +1,89 @@
+use std::io::Write;
+
+use crate::{bitboard::Bitboard, square::Square};
+
+const DIRECTIONS: [(i8, i8); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+pub fn generate_tables(f: &mut impl Write) {
+    generate_between(f);
+    generate_line(f);
+}
+
+pub fn generate_between(f: &mut impl Write) {
+    write!(f, "pub static BETWEEN: [[Bitboard; 64]; 64] = [").unwrap();
+
+    for a in 0..64 {
+        write!(f, "[").unwrap();
+
+        for b in 0..64 {
+            let mut bb = Bitboard::default();
+
+            if let Some((df, dr)) = direction(a, b) {
+                let mut sq = step(a, df, dr).unwrap();
+
+                while sq != b {
+                    bb |= Square::from_index(sq).into();
+                    sq = step(sq, df, dr).unwrap();
+                }
+            }
+
+            write!(f, "Bitboard({}),", bb.0).unwrap();
+        }
+
+        write!(f, "],").unwrap();
+    }
+
+    write!(f, "];").unwrap();
+}
+
+pub fn generate_line(f: &mut impl Write) {
+    write!(f, "pub static LINE: [[Bitboard; 64]; 64] = [").unwrap();
+
+    for a in 0..64 {
+        write!(f, "[").unwrap();
+
+        for b in 0..64 {
+            let mut bb = Bitboard::default();
+
+            if let Some((df, dr)) = direction(a, b) {
+                bb |= Square::from_index(a).into();
+
+                for (df, dr) in [(df, dr), (-df, -dr)] {
+                    let mut sq = a;
+
+                    while let Some(next) = step(sq, df, dr) {
+                        bb |= Square::from_index(next).into();
+                        sq = next;
+                    }
+                }
+            }
+
+            write!(f, "Bitboard({}),", bb.0).unwrap();
+        }
+
+        write!(f, "],").unwrap();
+    }
+
+    write!(f, "];").unwrap();
+}
+
+/// Get the direction from `a` to `b` if they are on the same rank, file or diagonal.
+fn direction(a: u8, b: u8) -> Option<(i8, i8)> {
+    DIRECTIONS.into_iter().find(|(df, dr)| {
+        let mut sq = a;
+
+        while let Some(next) = step(sq, *df, *dr) {
+            if next == b { return true }
+            sq = next;
+        }
+
+        false
+    })
+}
+
+fn step(sq: u8, df: i8, dr: i8) -> Option<u8> {
+    let file = (sq % 8) as i8 + df;
+    let rank = (sq / 8) as i8 + dr;
+
+    ((0..8).contains(&file) && (0..8).contains(&rank)).then_some((rank * 8 + file) as u8)
+}
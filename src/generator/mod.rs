@@ -3,6 +3,7 @@ use std::{env, fs::File, path::Path};
 mod bb_gen;
 mod king_gen;
 mod knight_gen;
+mod line_gen;
 mod magic_gen;
 mod pawn_gen;
 mod rays_gen;
@@ -25,6 +26,9 @@ pub fn main() {
     let king = Path::new(&out_dir).join("king.rs");
     king_gen::generate_tables(&mut File::create(king).unwrap());
 
+    let line = Path::new(&out_dir).join("line.rs");
+    line_gen::generate_tables(&mut File::create(line).unwrap());
+
     let magic = Path::new(&out_dir).join("magic.rs");
     magic_gen::generate_tables(&mut File::create(magic).unwrap(), rays);
 }
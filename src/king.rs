@@ -10,16 +10,31 @@ pub fn moves(square: Square) -> Bitboard {
     unsafe { *MOVES.get_unchecked(square.to_usize()) }
 }
 
-/// Get the squares required not to be under attacked when castling.
+/// Get the squares required not to be under attacked when castling. This includes the square the
+/// king is on and the square it will end up on.
 #[inline(always)]
 #[must_use]
 pub fn castle_path(color: Color, king_file: File, rook_file: File) -> Bitboard {
-    CASTLE_PATH[color as usize][king_file as usize][(king_file > rook_file) as usize]
+    CASTLE_PATH[color as usize][king_file as usize][rook_file as usize]
 }
 
-/// Get the squares required to be empty when castling.
+/// Get the squares required to be empty when castling. This excludes the square the king and the
+/// castling rook is on.
 #[inline(always)]
 #[must_use]
 pub fn castle_clearance(color: Color, king_file: File, rook_file: File) -> Bitboard {
-    CASTLE_CLEARANCE[color as usize][king_file as usize][(king_file > rook_file) as usize]
+    CASTLE_CLEARANCE[color as usize][king_file as usize][rook_file as usize]
+}
+
+/// Get the squares the king and the castling rook ends up on after castling, in that order.
+#[inline(always)]
+#[must_use]
+pub const fn castle_to(color: Color, king_file: File, rook_file: File) -> (Square, Square) {
+    let rank = color.back_rank();
+
+    if (king_file as u8) < (rook_file as u8) {
+        (Square::new(File::G, rank), Square::new(File::F, rank))
+    } else {
+        (Square::new(File::C, rank), Square::new(File::D, rank))
+    }
 }
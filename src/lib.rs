@@ -14,16 +14,28 @@
     clippy::as_conversions,
     clippy::cast_possible_truncation,
     clippy::cast_lossless,
+    clippy::copy_iterator,
     clippy::inline_always,
     clippy::unreadable_literal,
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod bitboard;
 pub mod board;
 pub mod castle_rights;
 pub mod chess_move;
 pub mod color;
+pub mod game;
+pub mod move_list;
+pub mod move_picker;
+#[cfg(feature = "alloc")]
+pub mod pgn;
 pub mod piece;
+pub mod polyglot;
 pub mod square;
 
 pub mod pawn;
@@ -39,6 +51,9 @@ pub mod prelude {
     pub use crate::castle_rights::*;
     pub use crate::chess_move::*;
     pub use crate::color::*;
+    pub use crate::game::*;
+    pub use crate::move_list::*;
+    pub use crate::move_picker::*;
     pub use crate::piece::*;
     pub use crate::square::*;
     pub use crate::{pawn, knight, bishop, rook, queen, king};
@@ -56,6 +71,12 @@ pub(crate) mod rays {
     include!(concat!(env!("OUT_DIR"), "/rays.rs"));
 }
 
+pub(crate) mod line {
+    use crate::bitboard::Bitboard;
+
+    include!(concat!(env!("OUT_DIR"), "/line.rs"));
+}
+
 pub(crate) mod magic {
     use crate::prelude::*;
 
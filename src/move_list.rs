@@ -0,0 +1,226 @@
+use core::{fmt, mem::MaybeUninit, ops::{Deref, DerefMut}};
+
+use crate::chess_move::Move;
+
+/// A fixed-capacity list of moves stored on the stack, so that moves can be collected, scored and
+/// sorted without an allocator.
+///
+/// # Example
+/// ```
+/// # use dychess::prelude::*;
+/// #
+/// let board = Board::default();
+/// let mut moves = MoveList::new();
+/// board.generate_into(&mut moves);
+///
+/// moves.retain(|m| m.from().rank() == Rank::_2);
+/// moves.sort_by_key(|m| m.to().to_u8());
+///
+/// assert_eq!(moves.len(), 16);
+/// assert_eq!(moves[0], Move::new(Square::A2, Square::A3, None));
+/// ```
+#[derive(Clone, Copy)]
+pub struct MoveList {
+    moves: [MaybeUninit<Move>; Self::CAPACITY],
+    len: usize,
+}
+
+impl MoveList {
+    /// The maximum number of moves a list can hold, which is enough for the pseudo-legal moves of
+    /// any reachable position. The maximum number of legal moves is 218.
+    pub const CAPACITY: usize = 256;
+
+    /// Create an empty list.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            moves: [MaybeUninit::uninit(); Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Append a move to the back of the list.
+    ///
+    /// # Panics
+    /// Panics if the list is already full.
+    #[inline(always)]
+    pub fn push(&mut self, mov: Move) {
+        assert!(self.try_push(mov).is_ok(), "move list is full");
+    }
+
+    /// Append a move to the back of the list, or give it back if the list is already full.
+    ///
+    /// # Errors
+    /// Returns the move if the list is already full.
+    #[inline(always)]
+    pub const fn try_push(&mut self, mov: Move) -> Result<(), Move> {
+        if self.len == Self::CAPACITY { return Err(mov) }
+
+        self.moves[self.len] = MaybeUninit::new(mov);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove the last move from the list and return it.
+    #[inline(always)]
+    pub const fn pop(&mut self) -> Option<Move> {
+        if self.len == 0 { return None }
+
+        self.len -= 1;
+        // SAFETY: moves before `len` are initialized
+        Some(unsafe { self.moves[self.len].assume_init() })
+    }
+
+    /// Remove all moves from the list.
+    #[inline(always)]
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Get the number of moves in the list.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize { self.len }
+
+    /// Get if the list contains no moves.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Get if the list can't hold any more moves.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_full(&self) -> bool { self.len == Self::CAPACITY }
+
+    /// Get the moves as a slice.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_slice(&self) -> &[Move] {
+        // SAFETY: moves before `len` are initialized, and `MaybeUninit<Move>` has the same layout
+        // as `Move`
+        unsafe { core::slice::from_raw_parts(self.moves.as_ptr().cast(), self.len) }
+    }
+
+    /// Get the moves as a mutable slice.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_mut_slice(&mut self) -> &mut [Move] {
+        // SAFETY: see `as_slice`
+        unsafe { core::slice::from_raw_parts_mut(self.moves.as_mut_ptr().cast(), self.len) }
+    }
+
+    /// Only keep the moves that satisfies the predicate, preserving their order.
+    #[inline(always)]
+    pub fn retain(&mut self, mut f: impl FnMut(&Move) -> bool) {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            // SAFETY: `i` < `len`
+            let mov = unsafe { self.moves[i].assume_init() };
+
+            if f(&mov) {
+                self.moves[kept] = MaybeUninit::new(mov);
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    /// Sort the moves in ascending order of the keys. The sort is stable, and uses insertion sort
+    /// since move lists are short.
+    #[inline(always)]
+    pub fn sort_by_key<K: Ord>(&mut self, mut f: impl FnMut(&Move) -> K) {
+        let moves = self.as_mut_slice();
+
+        for i in 1..moves.len() {
+            let mut j = i;
+
+            while j > 0 && f(&moves[j - 1]) > f(&moves[j]) {
+                moves.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+}
+
+impl Default for MoveList {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    #[inline(always)]
+    fn deref(&self) -> &[Move] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for MoveList {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [Move] {
+        self.as_mut_slice()
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = core::slice::Iter<'a, Move>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut MoveList {
+    type Item = &'a mut Move;
+    type IntoIter = core::slice::IterMut<'a, Move>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl Extend<Move> for MoveList {
+    /// Append the moves to the list.
+    ///
+    /// # Panics
+    /// Panics if the list overflows.
+    #[inline(always)]
+    fn extend<T: IntoIterator<Item = Move>>(&mut self, iter: T) {
+        for mov in iter {
+            self.push(mov);
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    #[inline(always)]
+    fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl PartialEq for MoveList {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for MoveList {}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{board::Board, chess_move::Move, move_list::MoveList, piece::Piece};
+
+/// A scoring function for moves used by [`MovePicker`]. Moves with higher scores are picked first.
+///
+/// This is implemented for every closure of `FnMut(Move) -> i32`.
+pub trait MoveScorer {
+    fn score(&mut self, mov: Move) -> i32;
+}
+
+impl<F: FnMut(Move) -> i32> MoveScorer for F {
+    #[inline(always)]
+    fn score(&mut self, mov: Move) -> i32 {
+        self(mov)
+    }
+}
+
+/// Scores captures by most valuable victim, then least valuable attacker. Non-captures are scored
+/// 0 and captures are always scored above them.
+#[derive(Debug, Clone, Copy)]
+pub struct MvvLva<'a>(pub &'a Board);
+
+impl MoveScorer for MvvLva<'_> {
+    #[inline(always)]
+    fn score(&mut self, mov: Move) -> i32 {
+        let board = self.0;
+        let Some(attacker) = board.piece_on(mov.from()) else { return 0 };
+        let victim = match board.piece_on(mov.to()) {
+            Some(victim) if board.color_on(mov.to()) != board.color_on(mov.from()) => victim,
+            // en passant
+            None if attacker == Piece::Pawn && mov.from().file() != mov.to().file() => Piece::Pawn,
+            _ => return 0,
+        };
+
+        (victim as i32 + 1) * 8 - attacker as i32
+    }
+}
+
+/// A move picker that yields moves in descending order of their scores.
+///
+/// Moves are scored once when the picker is created, and sorted lazily with selection sort, so that
+/// a search that cuts off early doesn't pay for sorting the rest of the moves.
+///
+/// # Example
+/// ```
+/// # use dychess::prelude::*;
+/// #
+/// let board = Board::from_epd(false, "4k3/8/8/3q4/4P3/2N5/8/4K3 w - -").unwrap();
+/// let mut picker = MovePicker::new(board.legal_moves(&[]), MvvLva(&board));
+///
+/// assert_eq!(picker.next(), Some(Move::new(Square::E4, Square::D5, None)));
+/// assert_eq!(picker.next(), Some(Move::new(Square::C3, Square::D5, None)));
+/// ```
+#[derive(Clone, Copy)]
+pub struct MovePicker {
+    moves: MoveList,
+    scores: [i32; MoveList::CAPACITY],
+    picked: usize,
+}
+
+impl MovePicker {
+    /// Collect and score the moves.
+    ///
+    /// # Panics
+    /// Panics if there are more than [`MoveList::CAPACITY`] moves.
+    #[inline(always)]
+    #[must_use]
+    pub fn new(moves: impl IntoIterator<Item = Move>, mut ordering: impl MoveScorer) -> Self {
+        let moves = moves.into_iter().collect::<MoveList>();
+        let mut scores = [0; MoveList::CAPACITY];
+
+        for (score, mov) in scores.iter_mut().zip(moves.iter()) {
+            *score = ordering.score(*mov);
+        }
+
+        Self { moves, scores, picked: 0 }
+    }
+
+    /// Get the next move with the highest score, along with its score. Moves with equal scores
+    /// are yielded in the order they were generated.
+    #[inline(always)]
+    pub fn next_scored(&mut self) -> Option<(Move, i32)> {
+        if self.picked == self.moves.len() { return None }
+
+        let mut best = self.picked;
+        for i in self.picked + 1..self.moves.len() {
+            if self.scores[i] > self.scores[best] {
+                best = i;
+            }
+        }
+
+        // shift the skipped moves instead of swapping, so that equal scores stay in order
+        let (mov, score) = (self.moves[best], self.scores[best]);
+        self.moves.copy_within(self.picked..best, self.picked + 1);
+        self.scores.copy_within(self.picked..best, self.picked + 1);
+        self.picked += 1;
+
+        Some((mov, score))
+    }
+
+    /// Get the moves that are not yet picked, in no particular order.
+    #[inline(always)]
+    #[must_use]
+    pub fn remaining(&self) -> &[Move] {
+        &self.moves[self.picked..]
+    }
+}
+
+impl Iterator for MovePicker {
+    type Item = Move;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Move> {
+        self.next_scored().map(|(mov, _)| mov)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.moves.len() - self.picked;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for MovePicker {}
+
+impl core::iter::FusedIterator for MovePicker {}
@@ -0,0 +1,667 @@
+use alloc::{borrow::ToOwned, format, string::{String, ToString}, vec, vec::Vec};
+use core::fmt;
+
+use crate::{
+    board::{Board, epd::{CastlingNotation, EpdError}, san::SanError},
+    chess_move::Move,
+    color::Color,
+    game::Outcome,
+};
+
+/// The result of a game as written in PGN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// The game is still in progress, abandoned or the result is otherwise unknown (`*`).
+    #[default]
+    Unknown,
+}
+
+impl GameResult {
+    /// Parse a game termination marker, e.g. `1-0` or `1/2-1/2`.
+    #[must_use]
+    pub fn from_pgn(result: &str) -> Option<Self> {
+        match result {
+            "1-0" => Some(Self::WhiteWins),
+            "0-1" => Some(Self::BlackWins),
+            "1/2-1/2" => Some(Self::Draw),
+            "*" => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Get the game termination marker of this result.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::WhiteWins => "1-0",
+            Self::BlackWins => "0-1",
+            Self::Draw => "1/2-1/2",
+            Self::Unknown => "*",
+        }
+    }
+}
+
+impl From<Outcome> for GameResult {
+    #[inline(always)]
+    fn from(outcome: Outcome) -> Self {
+        match outcome.winner() {
+            Some(Color::White) => Self::WhiteWins,
+            Some(Color::Black) => Self::BlackWins,
+            None => Self::Draw,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    UnexpectedChar(char),
+    UnexpectedEnd,
+    InvalidTag,
+    UnterminatedComment,
+    UnbalancedVariation,
+    InvalidFen(EpdError),
+    InvalidMove { san: String, error: SanError },
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedChar(ch) => write!(f, "unexpected character `{ch}`"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of PGN string"),
+            Self::InvalidTag => write!(f, "invalid tag pair"),
+            Self::UnterminatedComment => write!(f, "unterminated comment"),
+            Self::UnbalancedVariation => write!(f, "unbalanced variation parentheses"),
+            Self::InvalidFen(error) => write!(f, "invalid FEN tag: {error}"),
+            Self::InvalidMove { san, error } => write!(f, "invalid move `{san}`: {error}"),
+            #[cfg(feature = "std")]
+            Self::Io(kind) => write!(f, "I/O error: {kind}"),
+        }
+    }
+}
+
+impl core::error::Error for PgnError {}
+
+/// A move in the movetext of a game, with its annotations and the variations that can be played
+/// instead of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnMove {
+    pub mov: Move,
+    /// Numeric annotation glyphs, e.g. `1` for `!` and `2` for `?`.
+    pub nags: Vec<u8>,
+    /// The comment before the move. This is only used for the first move of a line.
+    pub comment_before: Option<String>,
+    /// The comment after the move.
+    pub comment: Option<String>,
+    /// Alternatives to this move, each starting from the position before this move.
+    pub variations: Vec<Vec<Self>>,
+}
+
+impl PgnMove {
+    /// Create a move without any annotations.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(mov: Move) -> Self {
+        Self { mov, nags: Vec::new(), comment_before: None, comment: None, variations: Vec::new() }
+    }
+}
+
+/// A game in Portable Game Notation.
+///
+/// # Example
+/// ```
+/// # use dychess::{pgn::*, prelude::*};
+/// #
+/// let game = PgnGame::parse(r#"
+/// [Event "Example"]
+/// [Result "1-0"]
+///
+/// 1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6?? 4. Qxf7# 1-0
+/// "#).unwrap();
+///
+/// assert_eq!(game.tag("Event"), Some("Example"));
+/// assert_eq!(game.moves.len(), 7);
+/// assert_eq!(game.result, GameResult::WhiteWins);
+/// assert!(game.board().legal_moves(&[]).next().is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The tag pairs in the order they appear.
+    pub tags: Vec<(String, String)>,
+    /// The starting position, which is given by the `FEN` tag if present.
+    pub start: Board,
+    /// The moves of the main line.
+    pub moves: Vec<PgnMove>,
+    /// The comment of a game without moves. Comments in games with moves are attached to the moves.
+    pub comment: Option<String>,
+    pub result: GameResult,
+}
+
+impl Default for PgnGame {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(Board::default())
+    }
+}
+
+impl PgnGame {
+    /// Create a game without tags and moves starting from the given position.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(start: Board) -> Self {
+        Self { tags: Vec::new(), start, moves: Vec::new(), comment: None, result: GameResult::Unknown }
+    }
+
+    /// Parse the first game in a PGN string.
+    ///
+    /// # Errors
+    /// Returns an error if the game is malformed or contains an illegal move, or
+    /// [`PgnError::UnexpectedEnd`] if the string doesn't contain a game.
+    pub fn parse(pgn: &str) -> Result<Self, PgnError> {
+        PgnReader::new(pgn).next().unwrap_or(Err(PgnError::UnexpectedEnd))
+    }
+
+    /// Get the value of a tag.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Set the value of a tag, replacing the existing value or adding it after the other tags.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        if let Some((_, v)) = self.tags.iter_mut().find(|(n, _)| n == name) {
+            value.clone_into(v);
+        } else {
+            self.tags.push((name.to_owned(), value.to_owned()));
+        }
+    }
+
+    /// Add a move to the end of the main line.
+    #[inline(always)]
+    pub fn push(&mut self, mov: Move) {
+        self.moves.push(PgnMove::new(mov));
+    }
+
+    /// Get an iterator of the main line moves along with the position before each of them.
+    pub fn mainline(&self) -> impl Iterator<Item = (Board, Move)> + '_ {
+        self.moves.iter().scan(self.start, |board, m| {
+            let before = *board;
+            board.make_move(m.mov);
+            Some((before, m.mov))
+        })
+    }
+
+    /// Get the position at the end of the main line.
+    #[must_use]
+    pub fn board(&self) -> Board {
+        self.mainline().last().map_or(self.start, |(board, mov)| board.copy_make_move(mov))
+    }
+}
+
+/// A reader of the games in a PGN string.
+///
+/// Games with errors are yielded as `Err`, after which the reader skips to the next tag section.
+///
+/// # Example
+/// ```
+/// # use dychess::pgn::*;
+/// #
+/// let pgn = "[White \"A\"]\n\n1. d4 d5 1/2-1/2\n\n[White \"B\"]\n\n1. e4 (1. c4) e5 *\n";
+/// let games = PgnReader::new(pgn).collect::<Result<Vec<_>, _>>().unwrap();
+///
+/// assert_eq!(games.len(), 2);
+/// assert_eq!(games[1].moves[0].variations.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PgnReader<'a> {
+    lexer: Lexer<'a>,
+}
+
+impl<'a> PgnReader<'a> {
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(pgn: &'a str) -> Self {
+        Self { lexer: Lexer { src: pgn, pos: 0 } }
+    }
+}
+
+impl Iterator for PgnReader<'_> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let game = parse_game(&mut self.lexer).transpose();
+
+        if matches!(game, Some(Err(_))) {
+            self.lexer.skip_game();
+        }
+
+        game
+    }
+}
+
+impl core::iter::FusedIterator for PgnReader<'_> {}
+
+/// A reader of the games from a buffered reader, which reads 1 game at a time.
+///
+/// Games with errors are yielded as `Err`, and reading continues from the next game.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PgnBufReader<R> {
+    reader: R,
+    pending: String,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> PgnBufReader<R> {
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(reader: R) -> Self {
+        Self { reader, pending: String::new() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> Iterator for PgnBufReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = core::mem::take(&mut self.pending);
+        let (mut in_movetext, mut last_blank) = (false, false);
+
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {},
+                Err(error) => return Some(Err(PgnError::Io(error.kind()))),
+            }
+
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') && (in_movetext || (last_blank && !text.trim().is_empty())) {
+                // the start of the next game
+                self.pending = line;
+                break;
+            }
+
+            in_movetext |= !trimmed.is_empty() && !trimmed.starts_with('[');
+            last_blank = trimmed.is_empty();
+            text.push_str(&line);
+        }
+
+        PgnReader::new(&text).next()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Tag(&'a str, String),
+    Symbol(&'a str),
+    Nag(u8),
+    Comment(&'a str),
+    Open,
+    Close,
+    Star,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn peek(&self) -> Option<Result<Token<'a>, PgnError>> {
+        let mut lexer = *self;
+        lexer.next_token()
+    }
+
+    fn next_token(&mut self) -> Option<Result<Token<'a>, PgnError>> {
+        loop {
+            let rest = &self.src[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            let ch = trimmed.chars().next()?;
+            let line_start = self.src[..self.pos].ends_with('\n') || self.pos == 0;
+
+            return Some(Ok(match ch {
+                // escaped lines are ignored
+                '%' if line_start => { self.take_until('\n'); continue },
+                '.' => { self.pos += 1; continue },
+                ';' => { self.pos += 1; Token::Comment(self.take_until('\n').trim()) },
+                '{' => {
+                    self.pos += 1;
+                    let Some(len) = self.src[self.pos..].find('}') else {
+                        self.pos = self.src.len();
+                        return Some(Err(PgnError::UnterminatedComment));
+                    };
+
+                    let comment = self.src[self.pos..self.pos + len].trim();
+                    self.pos += len + 1;
+                    Token::Comment(comment)
+                },
+                '(' => { self.pos += 1; Token::Open },
+                ')' => { self.pos += 1; Token::Close },
+                '*' => { self.pos += 1; Token::Star },
+                '$' => {
+                    self.pos += 1;
+                    match self.take_while(|c| c.is_ascii_digit()).parse() {
+                        Ok(nag) => Token::Nag(nag),
+                        Err(_) => return Some(Err(PgnError::UnexpectedChar('$'))),
+                    }
+                },
+                '!' | '?' => match self.take_while(|c| matches!(c, '!' | '?')) {
+                    "!" => Token::Nag(1),
+                    "?" => Token::Nag(2),
+                    "!!" => Token::Nag(3),
+                    "??" => Token::Nag(4),
+                    "!?" => Token::Nag(5),
+                    "?!" => Token::Nag(6),
+                    _ => return Some(Err(PgnError::UnexpectedChar(ch))),
+                },
+                '[' => return Some(self.tag()),
+                _ if is_symbol_char(ch) => Token::Symbol(self.take_while(is_symbol_char)),
+                _ => {
+                    self.pos += ch.len_utf8();
+                    return Some(Err(PgnError::UnexpectedChar(ch)));
+                },
+            }));
+        }
+    }
+
+    fn tag(&mut self) -> Result<Token<'a>, PgnError> {
+        self.pos += 1;
+        self.skip_spaces();
+
+        let name = self.take_while(is_symbol_char);
+        self.skip_spaces();
+        if name.is_empty() || !self.src[self.pos..].starts_with('"') { return Err(PgnError::InvalidTag) }
+        self.pos += 1;
+
+        let mut value = String::new();
+        let mut chars = self.src[self.pos..].char_indices();
+        loop {
+            match chars.next() {
+                Some((i, '"')) => { self.pos += i + 1; break },
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, ch @ ('"' | '\\'))) => value.push(ch),
+                    _ => return Err(PgnError::InvalidTag),
+                },
+                Some((_, '\n')) | None => return Err(PgnError::InvalidTag),
+                Some((_, ch)) => value.push(ch),
+            }
+        }
+
+        self.skip_spaces();
+        if !self.src[self.pos..].starts_with(']') { return Err(PgnError::InvalidTag) }
+        self.pos += 1;
+
+        Ok(Token::Tag(name, value))
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.src[self.pos..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn take_until(&mut self, end: char) -> &'a str {
+        self.take_while(|c| c != end)
+    }
+
+    fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Skip to the next line starting with a tag.
+    fn skip_game(&mut self) {
+        self.pos = self.src[self.pos..].find("\n[").map_or(self.src.len(), |i| self.pos + i + 1);
+    }
+}
+
+const fn is_symbol_char(ch: char) -> bool {
+    matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '+' | '#' | '=' | ':' | '-' | '/')
+}
+
+/// A line of moves being parsed.
+struct Line {
+    moves: Vec<PgnMove>,
+    board: Board,
+    /// The position before the last move, which is where variations start from.
+    before_last: Board,
+    comment: Option<String>,
+}
+
+impl Line {
+    const fn new(board: Board) -> Self {
+        Self { moves: Vec::new(), board, before_last: board, comment: None }
+    }
+}
+
+fn parse_game(lexer: &mut Lexer<'_>) -> Result<Option<PgnGame>, PgnError> {
+    let mut tags = Vec::new();
+    loop {
+        match lexer.peek() {
+            Some(Ok(Token::Tag(..))) => {},
+            None if tags.is_empty() => return Ok(None),
+            _ => break,
+        }
+
+        if let Some(Ok(Token::Tag(name, value))) = lexer.next_token() {
+            tags.push((name.to_owned(), value));
+        }
+    }
+
+    let chess960 = tags.iter().any(|(n, v)| n == "Variant" && is_chess960_variant(v));
+    let start = match tags.iter().find(|(n, _)| n == "FEN") {
+        Some((_, fen)) => Board::from_fen(chess960, fen).map_err(PgnError::InvalidFen)?,
+        None if chess960 => Board::from_epd(true, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -")
+            .map_err(PgnError::InvalidFen)?,
+        None => Board::default(),
+    };
+
+    let mut stack = vec![Line::new(start)];
+    let mut result = GameResult::Unknown;
+
+    loop {
+        // a tag without a result token starts the next game
+        if matches!(lexer.peek(), None | Some(Ok(Token::Tag(..)))) { break }
+
+        // SAFETY: `peek` returned a token
+        let token = unsafe { lexer.next_token().unwrap_unchecked() }?;
+        // SAFETY: the main line is never popped
+        let line = unsafe { stack.last_mut().unwrap_unchecked() };
+
+        match token {
+            Token::Comment(comment) => {
+                let target = match line.moves.last_mut() {
+                    Some(last) => &mut last.comment,
+                    None => &mut line.comment,
+                };
+
+                match target {
+                    Some(existing) => { existing.push(' '); existing.push_str(comment) },
+                    None => *target = Some(comment.to_owned()),
+                }
+            },
+            Token::Nag(nag) => if let Some(last) = line.moves.last_mut() { last.nags.push(nag) },
+            Token::Open => {
+                if line.moves.is_empty() { return Err(PgnError::UnbalancedVariation) }
+                let board = line.before_last;
+                stack.push(Line::new(board));
+            },
+            Token::Close => {
+                if stack.len() == 1 { return Err(PgnError::UnbalancedVariation) }
+                // SAFETY: checked above
+                let variation = unsafe { stack.pop().unwrap_unchecked() };
+                let parent = unsafe { stack.last_mut().unwrap_unchecked() };
+
+                if let Some(last) = parent.moves.last_mut() {
+                    last.variations.push(variation.moves);
+                }
+            },
+            Token::Star => break,
+            Token::Symbol(symbol) => {
+                if let Some(r) = GameResult::from_pgn(symbol) {
+                    result = r;
+                    break;
+                }
+
+                // move numbers
+                if symbol.bytes().all(|b| b.is_ascii_digit()) { continue }
+
+                let mov = line.board.parse_san(symbol)
+                    .map_err(|error| PgnError::InvalidMove { san: symbol.to_owned(), error })?;
+
+                line.before_last = line.board;
+                line.board.make_move(mov);
+                line.moves.push(PgnMove { comment_before: line.comment.take(), ..PgnMove::new(mov) });
+            },
+            Token::Tag(..) => unreachable!(),
+        }
+    }
+
+    if stack.len() != 1 { return Err(PgnError::UnbalancedVariation) }
+
+    // SAFETY: checked above
+    let Line { moves, comment, .. } = unsafe { stack.pop().unwrap_unchecked() };
+    Ok(Some(PgnGame { tags, start, moves, comment, result }))
+}
+
+/// Get if a `Variant` tag value is one of the spellings of Chess960.
+fn is_chess960_variant(variant: &str) -> bool {
+    ["Chess960", "Chess 960", "Fischerandom", "Fischer Random"]
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(variant))
+}
+
+/// Writes movetext tokens separated by spaces, wrapping lines at 80 columns.
+struct MovetextWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    column: usize,
+    /// Whether the next token follows an opening parenthesis.
+    open: bool,
+}
+
+impl MovetextWriter<'_, '_> {
+    const WIDTH: usize = 80;
+
+    fn token(&mut self, token: &str) -> fmt::Result {
+        let space = self.column != 0 && !self.open && token != ")";
+
+        if space && self.column + 1 + token.len() > Self::WIDTH {
+            self.f.write_str("\n")?;
+            self.column = 0;
+        } else if space {
+            self.f.write_str(" ")?;
+            self.column += 1;
+        }
+
+        self.f.write_str(token)?;
+        self.column += token.len();
+        self.open = token == "(";
+        Ok(())
+    }
+
+    /// Write a comment, dropping closing braces since they can't be escaped.
+    fn comment(&mut self, comment: &str) -> fmt::Result {
+        self.token(&format!("{{{}}}", comment.replace('}', "")))
+    }
+
+    fn line(&mut self, mut board: Board, moves: &[PgnMove]) -> fmt::Result {
+        let mut number = true;
+
+        for m in moves {
+            if let Some(comment) = &m.comment_before {
+                self.comment(comment)?;
+            }
+
+            if board.side_to_move() == Color::White {
+                self.token(&format!("{}.", board.fullmove_number()))?;
+            } else if number || m.comment_before.is_some() {
+                self.token(&format!("{}...", board.fullmove_number()))?;
+            }
+
+            self.token(&board.san(m.mov).to_string())?;
+            for nag in &m.nags {
+                self.token(&format!("${nag}"))?;
+            }
+
+            if let Some(comment) = &m.comment {
+                self.comment(comment)?;
+            }
+
+            for variation in &m.variations {
+                self.token("(")?;
+                self.line(board, variation)?;
+                self.token(")")?;
+            }
+
+            number = m.comment.is_some() || !m.variations.is_empty();
+            board.make_move(m.mov);
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PgnGame {
+    /// Write the game in PGN, with `SetUp`, `FEN` and `Variant` tags added if they are needed but
+    /// missing. Closing braces are dropped from comments.
+    ///
+    /// # Example
+    /// ```
+    /// # use dychess::{pgn::*, prelude::*};
+    /// #
+    /// let mut game = PgnGame::default();
+    /// game.set_tag("Event", "Example");
+    /// game.push(Move::new(Square::E2, Square::E4, None));
+    /// game.push(Move::new(Square::E7, Square::E5, None));
+    /// game.result = GameResult::Draw;
+    ///
+    /// assert_eq!(game.to_string(), "[Event \"Example\"]\n\n1. e4 e5 1/2-1/2\n");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            write!(f, "[{name} \"")?;
+            for ch in value.chars() {
+                if matches!(ch, '"' | '\\') { f.write_str("\\")? }
+                write!(f, "{ch}")?;
+            }
+            writeln!(f, "\"]")?;
+        }
+
+        if self.start.is_chess960() && self.tag("Variant").is_none() {
+            writeln!(f, "[Variant \"Chess960\"]")?;
+        }
+
+        let standard = Board::default();
+        if self.start != standard && self.tag("FEN").is_none() {
+            let castling = if self.start.is_chess960() { CastlingNotation::Shredder } else { CastlingNotation::XFen };
+            writeln!(f, "[SetUp \"1\"]")?;
+            writeln!(f, "[FEN \"{}\"]", self.start.display_fen(castling))?;
+        }
+
+        if !self.tags.is_empty() || self.start != standard { writeln!(f)? }
+
+        let mut writer = MovetextWriter { f, column: 0, open: false };
+        if let Some(comment) = &self.comment {
+            writer.comment(comment)?;
+        }
+        writer.line(self.start, &self.moves)?;
+        writer.token(self.result.as_str())?;
+        writeln!(writer.f)
+    }
+}
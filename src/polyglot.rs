@@ -0,0 +1,425 @@
+use core::fmt;
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{board::{Board, uci::convert_castling}, chess_move::Move, piece::Piece, square::Square};
+#[cfg(feature = "alloc")]
+use crate::{color::Color, pgn::{GameResult, PgnGame}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolyglotError {
+    /// The length of the book is not a multiple of [`PolyglotEntry::SIZE`].
+    InvalidLength,
+}
+
+impl fmt::Display for PolyglotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "book length is not a multiple of {}", PolyglotEntry::SIZE),
+        }
+    }
+}
+
+impl core::error::Error for PolyglotError {}
+
+/// An entry of a polyglot book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PolyglotEntry {
+    /// The hash of the position, which is the same as [`Board::get_hash`].
+    pub key: u64,
+    /// The move in the polyglot encoding, see [`decode_move`].
+    pub raw_move: u16,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+impl PolyglotEntry {
+    /// The size of an entry in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Read an entry from its big-endian representation.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let [k0, k1, k2, k3, k4, k5, k6, k7, m0, m1, w0, w1, l0, l1, l2, l3] = *bytes;
+
+        Self {
+            key: u64::from_be_bytes([k0, k1, k2, k3, k4, k5, k6, k7]),
+            raw_move: u16::from_be_bytes([m0, m1]),
+            weight: u16::from_be_bytes([w0, w1]),
+            learn: u32::from_be_bytes([l0, l1, l2, l3]),
+        }
+    }
+
+    /// Get the big-endian representation of this entry.
+    #[inline(always)]
+    #[must_use]
+    pub const fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let [k0, k1, k2, k3, k4, k5, k6, k7] = self.key.to_be_bytes();
+        let [m0, m1] = self.raw_move.to_be_bytes();
+        let [w0, w1] = self.weight.to_be_bytes();
+        let [l0, l1, l2, l3] = self.learn.to_be_bytes();
+
+        [k0, k1, k2, k3, k4, k5, k6, k7, m0, m1, w0, w1, l0, l1, l2, l3]
+    }
+
+    /// Decode the move of this entry, see [`decode_move`].
+    #[inline(always)]
+    #[must_use]
+    pub fn decode_move(&self, board: &Board) -> Option<Move> {
+        decode_move(board, self.raw_move)
+    }
+}
+
+/// Decode a move in the polyglot encoding, returning `None` if it is not legal on the board.
+///
+/// # Layout
+/// - Bits `12..=14` are the piece to promote to, `1` to `4` for knight, bishop, rook and queen.
+/// - Bits `6..=11` are the source square.
+/// - Bits `0..=5` are the destination square.
+///
+/// Castling is encoded as the king moving to the castling rook's square, which is converted to
+/// the encoding used by the board (see [`Board::make_move`]).
+///
+/// # Example
+/// ```
+/// # use dychess::{polyglot::decode_move, prelude::*};
+/// #
+/// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/4K2R w K -").unwrap();
+/// let e1h1 = (Square::E1.to_u8() as u16) << 6 | Square::H1.to_u8() as u16;
+///
+/// assert_eq!(decode_move(&board, e1h1), Some(Move::new(Square::E1, Square::G1, None)));
+/// ```
+#[must_use]
+pub fn decode_move(board: &Board, raw: u16) -> Option<Move> {
+    let to = Square::from_index(raw as u8 & 63);
+    let from = Square::from_index((raw >> 6) as u8 & 63);
+    let promotion = match (raw >> 12) & 7 {
+        0 => None,
+        p @ 1..=4 => Some(Piece::PROMOTE_TO[p as usize - 1]),
+        _ => return None,
+    };
+
+    if from == to { return None }
+
+    let mov = convert_castling(board, Move::new(from, to, promotion));
+    board.is_legal(mov).then_some(mov)
+}
+
+/// Encode a legal move in the polyglot encoding, see [`decode_move`].
+///
+/// # Example
+/// ```
+/// # use dychess::{polyglot::encode_move, prelude::*};
+/// #
+/// let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/4K2R w K -").unwrap();
+/// let e1h1 = (Square::E1.to_u8() as u16) << 6 | Square::H1.to_u8() as u16;
+///
+/// assert_eq!(encode_move(&board, Move::new(Square::E1, Square::G1, None)), e1h1);
+/// ```
+#[must_use]
+pub fn encode_move(board: &Board, mov: Move) -> u16 {
+    let to = if board.piece_on(mov.from()) == Some(Piece::King) {
+        board.castling_rook(board.side_to_move(), mov).unwrap_or_else(|| mov.to())
+    } else {
+        mov.to()
+    };
+    let promotion = mov.promotion().map_or(0, |p| p as u16);
+
+    (promotion << 12) | ((mov.from().to_u8() as u16) << 6) | to.to_u8() as u16
+}
+
+/// A polyglot opening book, which is a list of [`PolyglotEntry`]s sorted by their keys.
+///
+/// # Example
+/// ```
+/// # use dychess::{polyglot::*, prelude::*};
+/// #
+/// let board = Board::default();
+/// let e2e4 = (Square::E2.to_u8() as u16) << 6 | Square::E4.to_u8() as u16;
+/// let d2d4 = (Square::D2.to_u8() as u16) << 6 | Square::D4.to_u8() as u16;
+///
+/// let mut data = [0; 2 * PolyglotEntry::SIZE];
+/// data[..16].copy_from_slice(&PolyglotEntry { key: board.get_hash(), raw_move: e2e4, weight: 3, learn: 0 }.to_bytes());
+/// data[16..].copy_from_slice(&PolyglotEntry { key: board.get_hash(), raw_move: d2d4, weight: 1, learn: 0 }.to_bytes());
+///
+/// let book = PolyglotBook::new(&data).unwrap();
+/// assert_eq!(book.best_move(&board), Some(Move::new(Square::E2, Square::E4, None)));
+/// assert_eq!(book.weighted_move(&board, 3), Some(Move::new(Square::D2, Square::D4, None)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PolyglotBook<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PolyglotBook<'a> {
+    /// Create a book from the contents of a `.bin` file. The entries are assumed to be sorted.
+    ///
+    /// # Errors
+    /// Returns an error if the length of the data is not a multiple of [`PolyglotEntry::SIZE`].
+    #[inline(always)]
+    pub const fn new(data: &'a [u8]) -> Result<Self, PolyglotError> {
+        if !data.len().is_multiple_of(PolyglotEntry::SIZE) { return Err(PolyglotError::InvalidLength) }
+
+        Ok(Self { data })
+    }
+
+    /// Get the number of entries in the book.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.data.len() / PolyglotEntry::SIZE
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Get the entry at the given index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    #[inline(always)]
+    #[must_use]
+    pub fn entry(&self, idx: usize) -> PolyglotEntry {
+        let bytes = &self.data[idx * PolyglotEntry::SIZE..][..PolyglotEntry::SIZE];
+        PolyglotEntry::from_bytes(bytes.try_into().unwrap())
+    }
+
+    /// Get the entries of the position with the given hash by binary search.
+    #[must_use]
+    pub fn entries_for_hash(&self, hash: u64) -> PolyglotEntries<'a> {
+        let start = self.partition_point(|key| key < hash);
+        let end = start + Self { data: &self.data[start * PolyglotEntry::SIZE..] }.partition_point(|key| key == hash);
+
+        PolyglotEntries { data: &self.data[start * PolyglotEntry::SIZE..end * PolyglotEntry::SIZE] }
+    }
+
+    /// Get the entries of the position, including the ones with illegal moves.
+    #[inline(always)]
+    #[must_use]
+    pub fn entries(&self, board: &Board) -> PolyglotEntries<'a> {
+        self.entries_for_hash(board.get_hash())
+    }
+
+    /// Get the legal moves in the book for the position along with their weights.
+    #[inline(always)]
+    pub fn moves<'b>(&self, board: &'b Board) -> impl Iterator<Item = (Move, u16)> + 'b where 'a: 'b {
+        self.entries(board).filter_map(|e| Some((e.decode_move(board)?, e.weight)))
+    }
+
+    /// Get the book move with the highest weight. The first one is chosen if there are multiple.
+    #[must_use]
+    pub fn best_move(&self, board: &Board) -> Option<Move> {
+        self.moves(board)
+            .fold(None, |best: Option<(Move, u16)>, (mov, weight)| match best {
+                Some((_, best_weight)) if best_weight >= weight => best,
+                _ => Some((mov, weight)),
+            })
+            .map(|(mov, _)| mov)
+    }
+
+    /// Choose a book move with probability proportional to its weight, where `random` is a
+    /// uniformly distributed random number. Moves with 0 weight are never chosen.
+    #[must_use]
+    pub fn weighted_move(&self, board: &Board, random: u64) -> Option<Move> {
+        let total = self.moves(board).map(|(_, weight)| weight as u64).sum::<u64>();
+        if total == 0 { return None }
+
+        let mut pick = random % total;
+        self.moves(board).find(|(_, weight)| {
+            let found = pick < *weight as u64;
+            pick = pick.wrapping_sub(*weight as u64);
+            found
+        }).map(|(mov, _)| mov)
+    }
+
+    /// Find the number of entries from the start whose keys satisfy `pred`, assuming the entries
+    /// are partitioned by it.
+    fn partition_point(&self, pred: impl Fn(u64) -> bool) -> usize {
+        let (mut lo, mut hi) = (0, self.len());
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pred(self.entry(mid).key) { lo = mid + 1 } else { hi = mid }
+        }
+
+        lo
+    }
+}
+
+/// An iterator of the entries in a polyglot book for a position.
+///
+/// This is created by [`PolyglotBook::entries`] and [`PolyglotBook::entries_for_hash`].
+#[derive(Debug, Clone)]
+pub struct PolyglotEntries<'a> {
+    data: &'a [u8],
+}
+
+impl Iterator for PolyglotEntries<'_> {
+    type Item = PolyglotEntry;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<PolyglotEntry> {
+        let (entry, rest) = self.data.split_first_chunk()?;
+        self.data = rest;
+
+        Some(PolyglotEntry::from_bytes(entry))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.data.len() / PolyglotEntry::SIZE;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PolyglotEntries<'_> {}
+
+impl core::iter::FusedIterator for PolyglotEntries<'_> {}
+
+/// The accumulated statistics of a move in a position.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Default)]
+struct MoveStats {
+    count: u32,
+    weight: u64,
+    learn: u32,
+}
+
+/// A builder of polyglot books, which accumulates moves from games and writes them as sorted
+/// [`PolyglotEntry`]s.
+///
+/// The same move in the same position is merged into 1 entry by summing the weights and learn
+/// values. Weights that don't fit in 16 bits are scaled down along with the other moves of the
+/// position.
+///
+/// # Example
+/// ```
+/// # use dychess::{pgn::*, polyglot::*, prelude::*};
+/// #
+/// let mut builder = PolyglotBuilder::new().with_max_depth(2);
+/// builder.add_pgn(&PgnGame::parse("1. e4 e5 2. Nf3 1-0").unwrap());
+/// builder.add_pgn(&PgnGame::parse("1. e4 c5 1/2-1/2").unwrap());
+///
+/// let data = builder.to_bytes();
+/// let book = PolyglotBook::new(&data).unwrap();
+/// let board = Board::default();
+///
+/// assert_eq!(book.len(), 3);
+/// assert_eq!(book.moves(&board).collect::<Vec<_>>(), [(Move::new(Square::E2, Square::E4, None), 3)]);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct PolyglotBuilder {
+    moves: BTreeMap<(u64, u16), MoveStats>,
+    max_depth: Option<usize>,
+    min_count: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl PolyglotBuilder {
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { moves: BTreeMap::new(), max_depth: None, min_count: 0 }
+    }
+
+    /// Only add the first `plies` moves of each game.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_depth(mut self, plies: usize) -> Self {
+        self.max_depth = Some(plies);
+        self
+    }
+
+    /// Only write moves that are added at least `count` times.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_min_count(mut self, count: u32) -> Self {
+        self.min_count = count;
+        self
+    }
+
+    /// Add a move of the position.
+    pub fn add(&mut self, board: &Board, mov: Move, weight: u16, learn: u32) {
+        let stats = self.moves.entry((board.get_hash(), encode_move(board, mov))).or_default();
+
+        stats.count += 1;
+        stats.weight += weight as u64;
+        stats.learn = stats.learn.saturating_add(learn);
+    }
+
+    /// Replay a game from the starting position and add its moves. Moves of the winning side are
+    /// weighted 2, moves of both sides are weighted 1 in a draw, and 0 otherwise.
+    ///
+    /// # Panics
+    /// Panics if a move is not legal, see [`Board::make_move`].
+    pub fn add_game(&mut self, start: &Board, moves: impl IntoIterator<Item = Move>, result: GameResult) {
+        let mut board = *start;
+        let depth = self.max_depth.unwrap_or(usize::MAX);
+
+        for mov in moves.into_iter().take(depth) {
+            let weight = match (result, board.side_to_move()) {
+                (GameResult::WhiteWins, Color::White) | (GameResult::BlackWins, Color::Black) => 2,
+                (GameResult::Draw, _) => 1,
+                _ => 0,
+            };
+
+            self.add(&board, mov, weight, 0);
+            board.make_move(mov);
+        }
+    }
+
+    /// Add the main line of a game, see [`Self::add_game`].
+    #[inline(always)]
+    pub fn add_pgn(&mut self, game: &PgnGame) {
+        self.add_game(&game.start, game.moves.iter().map(|m| m.mov), game.result);
+    }
+
+    /// Get the entries of the book, sorted by their keys and then by descending weights.
+    #[must_use]
+    pub fn entries(&self) -> Vec<PolyglotEntry> {
+        let mut entries = Vec::new();
+        let mut moves = self.moves.iter().filter(|(_, stats)| stats.count >= self.min_count).peekable();
+
+        while let Some(&(&(key, _), _)) = moves.peek() {
+            let start = entries.len();
+            let mut max_weight = 0;
+
+            while let Some((&(_, raw_move), stats)) = moves.next_if(|((k, _), _)| *k == key) {
+                max_weight = max_weight.max(stats.weight);
+                entries.push((PolyglotEntry { key, raw_move, weight: 0, learn: stats.learn }, stats.weight));
+            }
+
+            for (entry, weight) in &mut entries[start..] {
+                entry.weight = if max_weight > u16::MAX as u64 {
+                    (*weight * u16::MAX as u64 / max_weight) as u16
+                } else {
+                    *weight as u16
+                };
+            }
+
+            entries[start..].sort_by_key(|(entry, _)| core::cmp::Reverse(entry.weight));
+        }
+
+        entries.into_iter().map(|(entry, _)| entry).collect()
+    }
+
+    /// Get the contents of the `.bin` file of the book.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.entries().iter().flat_map(PolyglotEntry::to_bytes).collect()
+    }
+
+    /// Write the `.bin` file of the book.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
@@ -0,0 +1,41 @@
+use dychess::{board::epd::CastlingNotation, prelude::*};
+
+static CHESS960_EPD: &str = include_str!("chess960.epd");
+
+#[test]
+fn xfen_round_trip() {
+    for line in CHESS960_EPD.lines() {
+        let (epd, _) = line.split_once(" ;D").unwrap();
+        let epd = epd.splitn(5, ' ').take(4).collect::<Vec<_>>().join(" ");
+        let board = Board::from_epd(true, &epd).expect(&epd);
+
+        assert_eq!(board.to_string(), epd);
+    }
+}
+
+#[test]
+fn shredder_round_trip() {
+    for (shredder, xfen) in [
+        ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf -", "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq -"),
+        ("rr2k1r1/8/8/8/8/8/8/RR2K1R1 w GBgb -", "rr2k1r1/8/8/8/8/8/8/RR2K1R1 w KBkb -"),
+        ("1r2k1rr/8/8/8/8/8/8/1R2K1RR w GBgb -", "1r2k1rr/8/8/8/8/8/8/1R2K1RR w GQgq -"),
+        ("2rkr1r1/8/8/8/8/8/8/2RKR1R1 w Ec -", "2rkr1r1/8/8/8/8/8/8/2RKR1R1 w Eq -"),
+        ("4k3/8/8/8/8/8/8/4K3 w - -", "4k3/8/8/8/8/8/8/4K3 w - -"),
+    ] {
+        let board = Board::from_epd(true, shredder).expect(shredder);
+
+        assert_eq!(board.display_epd(CastlingNotation::Shredder).to_string(), shredder);
+        assert_eq!(board.display_epd(CastlingNotation::XFen).to_string(), xfen);
+        assert_eq!(Board::from_epd(true, xfen).as_ref(), Ok(&board));
+    }
+}
+
+#[test]
+fn shredder_standard_chess() {
+    let board = Board::from_epd(false, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha -").unwrap();
+    assert_eq!(board, Board::default());
+    assert_eq!(
+        board.display_epd(CastlingNotation::Shredder).to_string(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha -",
+    );
+}
@@ -0,0 +1,23 @@
+// not every test uses every helper
+#![allow(dead_code)]
+
+use dychess::{board::epd::EpdRecord, prelude::*};
+
+static EPD: &str = include_str!("../standard.epd");
+static CHESS960_EPD: &str = include_str!("../chess960.epd");
+
+/// Call `f` with every record of the standard and Chess960 perft suites, and whether the record is
+/// Chess960.
+pub fn for_each_record(mut f: impl FnMut(&EpdRecord<'_>, bool)) {
+    for (chess960, epd) in [(false, EPD), (true, CHESS960_EPD)] {
+        for line in epd.lines() {
+            let record = EpdRecord::parse(chess960, line).expect(line);
+            f(&record, chess960);
+        }
+    }
+}
+
+/// Call `f` with the position of every record of the perft suites, and whether it is Chess960.
+pub fn for_each_position(mut f: impl FnMut(&Board, bool)) {
+    for_each_record(|record, chess960| f(record.board(), chess960));
+}
@@ -0,0 +1,57 @@
+use dychess::{board::epd::{EpdOperation, EpdRecord}, prelude::*};
+
+#[test]
+fn epd_operations() {
+    let record = EpdRecord::parse(
+        false,
+        r#"r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - bm O-O d3; am Ng5;id "test; one";ce -25; acd 12; pv O-O Nf6 d3; c0 "first comment"; c1 second;"#,
+    ).unwrap();
+
+    assert_eq!(record.board().to_string(), "r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq -");
+    assert_eq!(record.best_moves().collect::<Vec<_>>(), ["O-O", "d3"]);
+    assert_eq!(record.avoid_moves().collect::<Vec<_>>(), ["Ng5"]);
+    assert_eq!(record.id(), Some("test; one"));
+    assert_eq!(record.centipawn_eval(), Some(-25));
+    assert_eq!(record.analysis_depth(), Some(12));
+    assert_eq!(record.predicted_variation().collect::<Vec<_>>(), ["O-O", "Nf6", "d3"]);
+    assert_eq!(record.comment(0), Some("first comment"));
+    assert_eq!(record.comment(1), Some("second"));
+    assert_eq!(record.comment(2), None);
+    assert_eq!(record.perft(1), None);
+
+    assert_eq!(
+        record.to_string(),
+        r#"r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - bm O-O d3; am Ng5; id "test; one"; ce -25; acd 12; pv O-O Nf6 d3; c0 "first comment"; c1 second;"#,
+    );
+}
+
+#[test]
+fn epd_perft_counts() {
+    let record = EpdRecord::parse(
+        false,
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 3 17 ;D1 26 ;D2 568 ;D3 13744",
+    ).unwrap();
+
+    assert_eq!(record.board().halfmove_clock(), 3);
+    assert_eq!(record.board().fullmove_number(), 17);
+    assert_eq!(record.perft_counts().collect::<Vec<_>>(), [(1, 26), (2, 568), (3, 13744)]);
+    assert_eq!(record.perft(3), Some(13744));
+    assert_eq!(record.to_string(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - D1 26; D2 568; D3 13744;");
+}
+
+#[test]
+fn epd_new_record() {
+    let record = EpdRecord::new(Board::default(), r#"bm e4; id "start";"#);
+
+    assert_eq!(record.operation("bm"), Some(EpdOperation::new("bm", "e4")));
+    assert_eq!(EpdOperation::new("id", r#""start""#).to_string(), r#"id "start";"#);
+    assert_eq!(
+        record.to_string(),
+        r#"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm e4; id "start";"#,
+    );
+}
+
+#[test]
+fn epd_unclosed_quote() {
+    assert!(EpdRecord::parse(false, r#"4k3/8/8/8/8/8/8/4K3 w - - id "oops;"#).is_err());
+}
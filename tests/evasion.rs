@@ -0,0 +1,29 @@
+use dychess::prelude::*;
+
+#[test]
+fn single_check() {
+    let board = Board::from_epd(false, "4k3/8/8/8/8/2N5/8/r3K3 w - -").unwrap();
+
+    assert_eq!(board.pseudo_legal_moves(&[]).count(), 7);
+    assert!(board.pseudo_legal_moves(&[]).any(|m| m == Move::new(Square::C3, Square::B1, None)));
+    assert!(!board.pseudo_legal_moves(&[]).any(|m| m == Move::new(Square::C3, Square::E4, None)));
+    assert_eq!(board.legal_moves(&[]).count(), 5);
+}
+
+#[test]
+fn double_check() {
+    let board = Board::from_epd(false, "4k3/8/8/8/8/2N2n2/8/r3K3 w - -").unwrap();
+
+    assert!(board.pseudo_legal_moves(&[]).all(|m| m.from() == Square::E1));
+    assert!(board.legal_moves(&[]).all(|m| m.from() == Square::E1));
+}
+
+#[test]
+fn en_passant_evasion() {
+    let board = Board::from_epd(false, "8/8/8/2k5/3Pp3/8/8/4K3 b - d3").unwrap();
+    let ep = Move::new(Square::E4, Square::D3, None);
+
+    assert!(board.pseudo_legal_moves(&[]).any(|m| m == ep));
+    assert!(board.legal_moves(&[]).any(|m| m == ep));
+    assert!(!board.pseudo_legal_moves(&[]).any(|m| m == Move::new(Square::E4, Square::E3, None)));
+}
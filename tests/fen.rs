@@ -0,0 +1,73 @@
+use dychess::prelude::*;
+
+static EPD: &str = include_str!("standard.epd");
+
+#[test]
+fn fen_round_trip() {
+    for line in EPD.lines() {
+        let (fen, _) = line.split_once(" ;D").unwrap();
+        let board = Board::from_fen(false, fen).expect(fen);
+
+        assert_eq!(board.to_fen().to_string(), fen);
+    }
+}
+
+#[test]
+fn move_counters() {
+    let mut board = Board::default();
+
+    board.make_move(Move::new(Square::E2, Square::E4, None));
+    assert_eq!(board.to_fen().to_string(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+    board.make_move(Move::new(Square::G8, Square::F6, None));
+    assert_eq!(board.to_fen().to_string(), "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2");
+
+    board.make_move(Move::new(Square::G1, Square::F3, None));
+    assert_eq!(board.to_fen().to_string(), "rnbqkb1r/pppppppp/5n2/8/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 2 2");
+
+    // a null move leaves the halfmove clock alone
+    let restorer = board.null_move();
+    assert_eq!(board.to_fen().to_string(), "rnbqkb1r/pppppppp/5n2/8/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+    board.restore_null_move(restorer);
+
+    board.make_move(Move::new(Square::F6, Square::E4, None));
+    assert_eq!(board.to_fen().to_string(), "rnbqkb1r/pppppppp/8/8/4n3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 3");
+
+    let restorer = board.null_move();
+    assert_eq!(board.halfmove_clock(), 0);
+    assert_eq!(board.fullmove_number(), 3);
+
+    board.restore_null_move(restorer);
+    assert_eq!(board.to_fen().to_string(), "rnbqkb1r/pppppppp/8/8/4n3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 3");
+}
+
+#[test]
+fn fen_without_counters() {
+    let board = Board::from_fen(false, "4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+    assert_eq!(board.halfmove_clock(), 0);
+    assert_eq!(board.fullmove_number(), 1);
+
+    assert!(Board::from_fen(false, "4k3/8/8/8/8/8/8/4K3 w - - x 1").is_err());
+    assert!(Board::from_fen(false, "4k3/8/8/8/8/8/8/4K3 w - - 0 1 0").is_err());
+}
+
+#[test]
+fn counter_limits() {
+    let fen = "4k3/8/8/8/8/8/8/4K3 b - - 65535 65535";
+    let mut board = Board::from_fen(false, fen).unwrap();
+    let before = board;
+
+    let mov = Move::new(Square::E8, Square::D8, None);
+    let restorer = board.make_move(mov);
+    assert_eq!(board.halfmove_clock(), u16::MAX);
+    assert_eq!(board.fullmove_number(), u16::MAX);
+
+    board.unmake_move(mov, restorer);
+    assert_eq!(board, before);
+
+    let restorer = board.null_move();
+    assert_eq!(board.fullmove_number(), u16::MAX);
+
+    board.restore_null_move(restorer);
+    assert_eq!(board.to_fen().to_string(), fen);
+}
@@ -0,0 +1,320 @@
+use dychess::prelude::*;
+
+static EPD: &str = include_str!("standard.epd");
+
+const KNIGHT_DANCE: [(Square, Square); 4] = [
+    (Square::G1, Square::F3),
+    (Square::G8, Square::F6),
+    (Square::F3, Square::G1),
+    (Square::F6, Square::G8),
+];
+
+fn push_all<const N: usize>(game: &mut Game<N>, moves: &[(Square, Square)]) {
+    for (from, to) in moves {
+        game.push(Move::new(*from, *to, None));
+    }
+}
+
+#[test]
+fn push_and_pop() {
+    for line in EPD.lines() {
+        let (fen, _) = line.split_once(" ;D").unwrap();
+        let start = Board::from_fen(false, fen).unwrap();
+        let mut game = Game::<64>::new(start);
+        let mut boards = vec![start];
+
+        for i in 0..64 {
+            let Some(mov) = game.board().legal_moves(&[]).nth(i % 5) else { break };
+            game.push(mov);
+            boards.push(*game.board());
+
+            assert_eq!(game.hashes().last(), Some(&boards[boards.len() - 2].get_hash()));
+        }
+
+        assert_eq!(game.len(), boards.len() - 1);
+        assert_eq!(game.moves().count(), game.len());
+
+        while !game.is_empty() {
+            boards.pop();
+            game.pop();
+            assert_eq!(game.board(), boards.last().unwrap());
+        }
+
+        assert_eq!(game.pop(), None);
+    }
+}
+
+#[test]
+fn capacity() {
+    let mut game = Game::<4>::default();
+    push_all(&mut game, &KNIGHT_DANCE);
+
+    assert!(game.is_full());
+    let mov = Move::new(Square::G1, Square::F3, None);
+    assert_eq!(game.try_push(mov), Err(mov));
+    assert_eq!(game.board().get_hash(), Board::default().get_hash());
+    assert_eq!(game.moves().next_back(), Some(Move::new(Square::F6, Square::G8, None)));
+}
+
+#[test]
+#[should_panic = "game history is full"]
+fn push_when_full() {
+    let mut game = Game::<0>::default();
+    game.push(Move::new(Square::G1, Square::F3, None));
+}
+
+#[test]
+fn repetitions() {
+    let mut game = Game::<64>::default();
+    assert!(game.is_repetition(1));
+    assert!(!game.is_repetition(2));
+
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_repetition(2));
+    assert!(!game.is_threefold());
+
+    push_all(&mut game, &KNIGHT_DANCE[..2]);
+    assert!(game.is_repetition(2));
+    assert!(!game.is_threefold());
+
+    push_all(&mut game, &KNIGHT_DANCE[2..]);
+    assert!(game.is_threefold());
+    assert!(!game.is_repetition(4));
+
+    push_all(&mut game, &KNIGHT_DANCE);
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_repetition(5));
+    assert!(!game.is_repetition(6));
+}
+
+#[test]
+fn repetitions_with_other_side_to_move() {
+    // the same placement with a different side to move is not a repetition
+    let start = Board::from_epd(false, "4k3/8/8/8/8/8/8/R3K3 w - -").unwrap();
+    let mut game = Game::<64>::new(start);
+    push_all(&mut game, &[
+        (Square::A1, Square::A2),
+        (Square::E8, Square::D8),
+        (Square::A2, Square::A3),
+        (Square::D8, Square::E8),
+        (Square::A3, Square::A1),
+    ]);
+
+    assert_eq!(game.board().combined(), start.combined());
+    assert!(game.hashes().contains(&start.get_hash()));
+    assert!(!game.is_repetition(2));
+
+    push_all(&mut game, &[(Square::E8, Square::D8), (Square::A1, Square::A3)]);
+    assert!(game.is_repetition(2));
+}
+
+#[test]
+fn irreversible_moves() {
+    let mut game = Game::<64>::default();
+    push_all(&mut game, &KNIGHT_DANCE);
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_threefold());
+
+    // a pawn move makes the earlier positions unreachable
+    push_all(&mut game, &[(Square::E2, Square::E4), (Square::E7, Square::E5)]);
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_repetition(2));
+    assert!(!game.is_threefold());
+
+    // losing castling rights changes the hash
+    let mut game = Game::<64>::new(Board::from_epd(false, "r3k3/8/8/8/8/8/8/R3K3 w Qq -").unwrap());
+    push_all(&mut game, &[
+        (Square::E1, Square::D1),
+        (Square::E8, Square::D8),
+        (Square::D1, Square::E1),
+        (Square::D8, Square::E8),
+    ]);
+    assert!(!game.is_repetition(2));
+
+    push_all(&mut game, &[
+        (Square::E1, Square::D1),
+        (Square::E8, Square::D8),
+        (Square::D1, Square::E1),
+        (Square::D8, Square::E8),
+    ]);
+    assert!(game.is_repetition(2));
+}
+
+#[test]
+fn history_before_start() {
+    // the halfmove clock from the FEN can be larger than the history
+    let mut game = Game::<64>::new(Board::from_fen(false, "4k3/8/8/8/8/8/8/4K3 w - - 40 80").unwrap());
+    push_all(&mut game, &[(Square::E1, Square::D1), (Square::E8, Square::D8), (Square::D1, Square::E1), (Square::D8, Square::E8)]);
+
+    assert!(game.is_repetition(2));
+    assert!(!game.is_threefold());
+}
+
+#[test]
+fn upcoming_repetitions() {
+    let mut game = Game::<64>::default();
+    push_all(&mut game, &KNIGHT_DANCE[..3]);
+
+    // Nf6-g8 repeats the starting position, which is before the root unless the ply says otherwise
+    assert!(game.has_upcoming_repetition(4));
+    assert!(!game.has_upcoming_repetition(3));
+    assert!(!game.has_upcoming_repetition(0));
+
+    // once the starting position has occurred twice, it counts at the root too
+    push_all(&mut game, &KNIGHT_DANCE[3..]);
+    push_all(&mut game, &KNIGHT_DANCE[..3]);
+    assert!(game.has_upcoming_repetition(0));
+
+    // only the side to move can go back
+    push_all(&mut game, &KNIGHT_DANCE[3..]);
+    let mut game = Game::<64>::new(*game.board());
+    push_all(&mut game, &KNIGHT_DANCE[..2]);
+    assert!(!game.has_upcoming_repetition(0));
+    assert!(!game.has_upcoming_repetition(100));
+}
+
+#[test]
+fn upcoming_repetitions_of_sliders() {
+    let moves = [
+        (Square::A1, Square::B1),
+        (Square::E8, Square::D8),
+        (Square::B1, Square::B4),
+        (Square::D8, Square::E8),
+        (Square::B4, Square::A4),
+    ];
+
+    // Ra4-a1 would repeat the starting position
+    let mut game = Game::<64>::new(Board::from_epd(false, "4k3/8/8/8/8/8/8/R6K w - -").unwrap());
+    push_all(&mut game, &moves);
+    assert!(game.has_upcoming_repetition(6));
+    assert!(!game.has_upcoming_repetition(5));
+
+    // unless the path is blocked
+    let mut game = Game::<64>::new(Board::from_epd(false, "4k3/8/8/8/8/8/P7/R6K w - -").unwrap());
+    push_all(&mut game, &moves);
+    assert!(!game.has_upcoming_repetition(6));
+
+    // positions before the start of the history are unknown
+    let mut game = Game::<64>::new(Board::from_epd(false, "4k3/8/8/8/8/8/8/R6K w - -").unwrap());
+    push_all(&mut game, &moves[..4]);
+    push_all(&mut game, &[(Square::B4, Square::A4), (Square::E8, Square::D7), (Square::A4, Square::A1)]);
+    assert!(game.has_upcoming_repetition(100));
+    let mut game = Game::<64>::new(*game.board());
+    assert!(!game.has_upcoming_repetition(100));
+    push_all(&mut game, &[(Square::D7, Square::D6)]);
+    assert!(!game.has_upcoming_repetition(100));
+}
+
+#[test]
+fn upcoming_repetitions_match_move_generation() {
+    for line in EPD.lines() {
+        let (fen, _) = line.split_once(" ;D").unwrap();
+        let mut game = Game::<64>::new(Board::from_fen(false, fen).unwrap());
+
+        for i in 0..64 {
+            let board = *game.board();
+            let window = (board.halfmove_clock() as usize).min(game.len());
+            let history = &game.hashes()[game.len() - window..];
+
+            // a quiet move reaching a position from the history with the same side to move
+            let repeats = board.legal_moves(&[]).any(|m| {
+                let after = board.copy_make_move(m).get_hash();
+                board.piece_on(m.from()) != Some(Piece::Pawn)
+                    && board.piece_on(m.to()).is_none()
+                    && history.iter().rev().step_by(2).any(|h| *h == after)
+            });
+
+            if repeats {
+                assert!(game.has_upcoming_repetition(usize::MAX), "{fen} {:?}", game.moves().collect::<Vec<_>>());
+            }
+
+            // shuffle back and forth with non-pawn quiet moves to make repetitions likely
+            let Some(mov) = board
+                .legal_moves(&[])
+                .filter(|m| board.piece_on(m.from()) != Some(Piece::Pawn) && board.piece_on(m.to()).is_none())
+                .nth(i % 3)
+                .or_else(|| board.legal_moves(&[]).next())
+            else {
+                break;
+            };
+            game.push(mov);
+        }
+    }
+}
+
+fn status_of(fen: &str) -> GameStatus {
+    Game::<1>::new(Board::from_fen(false, fen).unwrap()).status()
+}
+
+#[test]
+fn checkmate_and_stalemate() {
+    assert_eq!(status_of("7k/5Q2/6K1/8/8/8/8/8 w - -"), GameStatus::Ongoing);
+    assert_eq!(status_of("7k/5Q2/6K1/8/8/8/8/8 b - -"), GameStatus::Over(Outcome::Stalemate));
+    assert_eq!(
+        status_of("5Q1k/8/6K1/8/8/8/8/8 b - -"),
+        GameStatus::Over(Outcome::Checkmate { winner: Color::White }),
+    );
+    assert_eq!(
+        status_of("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"),
+        GameStatus::Over(Outcome::Checkmate { winner: Color::Black }),
+    );
+}
+
+#[test]
+fn move_rules() {
+    let mut game = Game::<4>::new(Board::from_fen(false, "4k3/8/8/8/8/8/8/R3K3 w - - 98 100").unwrap());
+    assert_eq!(game.status(), GameStatus::Ongoing);
+
+    game.push(Move::new(Square::A1, Square::A2, None));
+    game.push(Move::new(Square::E8, Square::D8, None));
+    assert_eq!(game.status(), GameStatus::DrawClaimable(Outcome::FiftyMoveRule));
+
+    let mut game = Game::<4>::new(Board::from_fen(false, "4k3/8/8/8/8/8/8/R3K3 w - - 149 100").unwrap());
+    assert_eq!(game.status(), GameStatus::DrawClaimable(Outcome::FiftyMoveRule));
+
+    game.push(Move::new(Square::A1, Square::A2, None));
+    assert_eq!(game.status(), GameStatus::Over(Outcome::SeventyFiveMoveRule));
+
+    // checkmate on the last move takes precedence
+    let mut game = Game::<4>::new(Board::from_fen(false, "7k/8/6K1/8/8/8/8/5Q2 w - - 149 100").unwrap());
+    game.push(Move::new(Square::F1, Square::F8, None));
+    assert_eq!(game.board().halfmove_clock(), 150);
+    assert_eq!(game.status(), GameStatus::Over(Outcome::Checkmate { winner: Color::White }));
+}
+
+#[test]
+fn repetition_rules() {
+    let mut game = Game::<64>::default();
+
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert_eq!(game.status(), GameStatus::Ongoing);
+
+    push_all(&mut game, &KNIGHT_DANCE);
+    assert_eq!(game.status(), GameStatus::DrawClaimable(Outcome::ThreefoldRepetition));
+
+    push_all(&mut game, &KNIGHT_DANCE);
+    push_all(&mut game, &KNIGHT_DANCE[..3]);
+    assert_eq!(game.status(), GameStatus::DrawClaimable(Outcome::ThreefoldRepetition));
+
+    push_all(&mut game, &KNIGHT_DANCE[3..]);
+    assert_eq!(game.status(), GameStatus::Over(Outcome::FivefoldRepetition));
+}
+
+#[test]
+fn dead_positions() {
+    assert_eq!(status_of("4k3/8/8/8/8/8/8/4K3 w - -"), GameStatus::Over(Outcome::DeadPosition));
+    assert_eq!(status_of("4k3/8/8/8/8/8/8/2B1K3 w - -"), GameStatus::Over(Outcome::DeadPosition));
+    assert_eq!(status_of("4k3/8/8/8/8/8/8/4K1n1 w - -"), GameStatus::Over(Outcome::DeadPosition));
+    assert_eq!(status_of("2b1k3/8/8/8/8/8/8/3BK3 w - -"), GameStatus::Over(Outcome::DeadPosition));
+    assert_eq!(status_of("4k3/8/8/8/8/8/8/1N2KN2 w - -"), GameStatus::Ongoing);
+    assert_eq!(status_of("3bk3/8/8/8/8/8/8/3BK3 w - -"), GameStatus::Ongoing);
+    assert_eq!(status_of("4k3/8/8/8/8/8/7p/4K3 w - -"), GameStatus::Ongoing);
+}
+
+#[test]
+fn outcome_winner() {
+    assert_eq!(Outcome::Checkmate { winner: Color::Black }.winner(), Some(Color::Black));
+    assert!(!Outcome::Checkmate { winner: Color::White }.is_draw());
+    assert!(Outcome::Stalemate.is_draw());
+    assert!(Outcome::FiftyMoveRule.is_draw());
+}
@@ -0,0 +1,42 @@
+use dychess::prelude::*;
+
+mod common;
+
+#[test]
+fn matches_make_move() {
+    common::for_each_position(|board, _| check_tree(board, 2));
+}
+
+#[test]
+fn edge_cases() {
+    for (chess960, epd) in [
+        // castling gives check with the rook
+        (false, "5k2/8/8/8/8/8/8/4K2R w K -"),
+        (true, "3k4/8/8/8/8/8/8/1R2K3 w Q -"),
+        // en passant discovers check along a rank and a diagonal
+        (false, "8/8/8/k1pP3R/8/8/8/4K3 w - c6"),
+        (false, "6B1/8/8/2pP4/8/8/k7/4K3 w - c6"),
+        // underpromotions give check and discover check
+        (false, "8/1P1k4/8/8/8/8/8/4K3 w - -"),
+        (false, "8/RP5k/8/8/8/8/8/4K3 w - -"),
+        // the king discovers check, and double checks
+        (false, "7k/8/8/8/3K4/8/8/B7 w - -"),
+        (false, "3k4/8/8/8/3N4/8/8/3RK3 w - -"),
+    ] {
+        let board = Board::from_epd(chess960, epd).expect(epd);
+
+        assert!(board.legal_moves(&[]).any(|m| board.gives_check(m)), "{board}");
+        check_tree(&board, 2);
+    }
+}
+
+fn check_tree(board: &Board, depth: usize) {
+    for m in board.legal_moves(&[]) {
+        let after = board.copy_make_move(m);
+        assert_eq!(board.gives_check(m), after.is_check(), "{board} {m}");
+
+        if depth > 1 {
+            check_tree(&after, depth - 1);
+        }
+    }
+}
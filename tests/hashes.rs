@@ -0,0 +1,82 @@
+use dychess::prelude::*;
+
+mod common;
+
+#[test]
+fn incremental_hashes() {
+    common::for_each_position(|board, chess960| check_moves(chess960, board, 2));
+
+    for (chess960, epd) in [
+        // promotions with captures, and promoting to a piece there are already several of
+        (false, "rn2k3/1P6/8/8/8/8/1p3QQQ/RN2K3 w Qq -"),
+        (false, "rn2k3/1P6/8/8/8/8/1p3QQQ/RN2K3 b Qq -"),
+        // en passant on both sides
+        (false, "4k3/8/8/2pP4/4pP2/8/8/4K3 w - c6"),
+        (false, "4k3/8/8/2pP4/4pP2/8/8/4K3 b - f3"),
+        // Chess960 castling where the king moves onto the rook's square
+        (true, "1k6/8/8/8/8/8/8/4RKR1 w GE -"),
+    ] {
+        check_moves(chess960, &Board::from_epd(chess960, epd).expect(epd), 2);
+    }
+}
+
+fn check_moves(chess960: bool, board: &Board, depth: usize) {
+    if depth == 0 { return }
+
+    for m in board.legal_moves(&[]) {
+        let after = board.copy_make_move(m);
+        let parsed = Board::from_fen(chess960, &after.to_fen().to_string()).unwrap();
+
+        assert_eq!(after.pawn_hash(), parsed.pawn_hash(), "{board} {m}");
+        assert_eq!(after.material_hash(), parsed.material_hash(), "{board} {m}");
+        assert_eq!(after.non_pawn_hash(Color::White), parsed.non_pawn_hash(Color::White), "{board} {m}");
+        assert_eq!(after.non_pawn_hash(Color::Black), parsed.non_pawn_hash(Color::Black), "{board} {m}");
+
+        let moved = board.piece_on(m.from());
+        let captured = board.piece_on(m.to()).filter(|_| board.color_on(m.to()) != board.color_on(m.from()));
+        let us = board.side_to_move();
+
+        if moved != Some(Piece::Pawn) && captured != Some(Piece::Pawn) {
+            assert_eq!(after.pawn_hash(), board.pawn_hash(), "{board} {m}");
+        }
+        if moved == Some(Piece::Pawn) && m.promotion().is_none() {
+            assert_eq!(after.non_pawn_hash(us), board.non_pawn_hash(us), "{board} {m}");
+        }
+        if captured.is_none_or(|p| p == Piece::Pawn) {
+            assert_eq!(after.non_pawn_hash(!us), board.non_pawn_hash(!us), "{board} {m}");
+        }
+        if after.combined().popcnt() == board.combined().popcnt() && m.promotion().is_none() {
+            assert_eq!(after.material_hash(), board.material_hash(), "{board} {m}");
+        } else {
+            assert_ne!(after.material_hash(), board.material_hash(), "{board} {m}");
+        }
+
+        check_moves(chess960, &after, depth - 1);
+    }
+}
+
+#[test]
+fn material_hash() {
+    let material_hash = |fen| Board::from_epd(false, fen).unwrap().material_hash();
+
+    // only the number of each piece of each color matters
+    assert_eq!(material_hash("4k3/pp6/8/8/8/8/PPN5/4K3 w - -"), material_hash("4k3/8/p7/1p5N/8/3P4/4P3/K7 b - -"));
+    assert_ne!(material_hash("4k3/pp6/8/8/8/8/PPN5/4K3 w - -"), material_hash("4k3/pp6/8/8/8/8/PPB5/4K3 w - -"));
+    assert_ne!(material_hash("4k3/pp6/8/8/8/8/PPN5/4K3 w - -"), material_hash("4k3/pp6/8/8/8/8/PPn5/4K3 w - -"));
+    assert_ne!(material_hash("4k3/pp6/8/8/8/8/PPN5/4K3 w - -"), material_hash("4k3/ppp5/8/8/8/8/PPN5/4K3 w - -"));
+    assert_ne!(material_hash("4k3/8/8/8/8/8/NN6/4K3 w - -"), material_hash("4k3/8/8/8/8/8/N7/4K3 w - -"));
+}
+
+#[test]
+fn pawn_and_non_pawn_hashes() {
+    let a = Board::from_epd(false, "r3k3/1p6/8/8/8/8/1P6/R3K3 w - -").unwrap();
+    let b = Board::from_epd(false, "4k2r/1p6/8/8/8/8/1P6/3K3R b - -").unwrap();
+    let c = Board::from_epd(false, "r3k3/2p5/8/8/8/8/1P6/R3K3 w - -").unwrap();
+
+    assert_eq!(a.pawn_hash(), b.pawn_hash());
+    assert_ne!(a.pawn_hash(), c.pawn_hash());
+    assert_ne!(a.non_pawn_hash(Color::White), b.non_pawn_hash(Color::White));
+    assert_eq!(a.non_pawn_hash(Color::White), c.non_pawn_hash(Color::White));
+    assert_eq!(a.non_pawn_hash(Color::Black), c.non_pawn_hash(Color::Black));
+    assert_ne!(a.non_pawn_hash(Color::White), a.non_pawn_hash(Color::Black));
+}
@@ -0,0 +1,39 @@
+use dychess::prelude::*;
+
+#[test]
+fn between_matches_slider_rays() {
+    for a in (0..64).map(Square::from_index) {
+        for b in (0..64).map(Square::from_index) {
+            let expected = if a == b {
+                Bitboard::default()
+            } else if !(rook::rays(a) & b.into()).is_empty() {
+                rook::moves(a, b.into()) & rook::moves(b, a.into())
+            } else if !(bishop::rays(a) & b.into()).is_empty() {
+                bishop::moves(a, b.into()) & bishop::moves(b, a.into())
+            } else {
+                Bitboard::default()
+            };
+
+            assert_eq!(Bitboard::between(a, b), expected, "{a} {b}");
+        }
+    }
+}
+
+#[test]
+fn line_contains_both_squares() {
+    for a in (0..64).map(Square::from_index) {
+        for b in (0..64).map(Square::from_index) {
+            let line = Bitboard::line(a, b);
+            let aligned = a != b && !((rook::rays(a) | bishop::rays(a)) & b.into()).is_empty();
+
+            assert_eq!(!line.is_empty(), aligned, "{a} {b}");
+            if !aligned { continue }
+
+            assert_eq!(line, Bitboard::line(b, a), "{a} {b}");
+            assert_eq!(line & Bitboard::between(a, b), Bitboard::between(a, b), "{a} {b}");
+            assert!(!(line & a.into()).is_empty() && !(line & b.into()).is_empty(), "{a} {b}");
+            assert!(line.popcnt() >= 2 && line.popcnt() <= 8, "{a} {b}");
+            assert_eq!(line & (rook::rays(a) | bishop::rays(a)) | a.into(), line, "{a} {b}");
+        }
+    }
+}
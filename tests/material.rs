@@ -0,0 +1,78 @@
+use dychess::prelude::*;
+
+fn board(epd: &str) -> Board {
+    Board::from_epd(false, epd).unwrap()
+}
+
+#[test]
+fn square_colors() {
+    assert_eq!(Bitboard::LIGHT_SQUARES | Bitboard::DARK_SQUARES, !Bitboard::default());
+    assert!((Bitboard::LIGHT_SQUARES & Bitboard::DARK_SQUARES).is_empty());
+
+    for sq in [Square::A1, Square::C1, Square::B2, Square::H8, Square::E5, Square::D4] {
+        assert!(!(Bitboard::DARK_SQUARES & sq.into()).is_empty(), "{sq}");
+    }
+    for sq in [Square::B1, Square::H1, Square::A8, Square::D5, Square::E4] {
+        assert!(!(Bitboard::LIGHT_SQUARES & sq.into()).is_empty(), "{sq}");
+    }
+}
+
+#[test]
+fn insufficient_material() {
+    for epd in [
+        "4k3/8/8/8/8/8/8/4K3 w - -",
+        "4k3/8/8/8/8/8/8/2B1K3 w - -",
+        "4k3/8/8/8/8/8/8/1N2K3 b - -",
+        "4kn2/8/8/8/8/8/8/4K3 w - -",
+        // same-colored bishops, on either side
+        "2b1k3/8/8/8/8/8/8/3BK3 w - -",
+        "4k3/8/8/8/8/8/8/1B1BKB1B w - -",
+        "1b2k3/b7/8/8/8/8/8/2B1K3 w - -",
+    ] {
+        assert!(board(epd).is_insufficient_material(), "{epd}");
+    }
+
+    for epd in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+        "4k3/8/8/8/8/8/8/1N2KN2 w - -",
+        "4k3/8/8/8/8/8/8/1NB1K3 w - -",
+        "4kn2/8/8/8/8/8/8/2B1K3 w - -",
+        // opposite-colored bishops
+        "3bk3/8/8/8/8/8/8/3BK3 w - -",
+        "4k3/8/8/8/8/8/8/2BBK3 w - -",
+        "4k3/7p/8/8/8/8/8/4K3 w - -",
+        "4k3/8/8/8/8/8/8/R3K3 w - -",
+        "4k3/8/8/8/8/8/8/3QK3 w - -",
+    ] {
+        assert!(!board(epd).is_insufficient_material(), "{epd}");
+    }
+}
+
+#[test]
+fn cannot_win() {
+    for (epd, white, black) in [
+        ("4k3/8/8/8/8/8/8/4K3 w - -", true, true),
+        ("4k3/8/8/8/8/8/8/1N2K3 w - -", true, true),
+        // a single knight can mate if the opponent blocks its own king
+        ("4k3/4p3/8/8/8/8/8/1N2K3 w - -", false, false),
+        ("4kr2/8/8/8/8/8/8/1N2K3 w - -", false, false),
+        ("4kq2/8/8/8/8/8/8/1N2K3 w - -", true, false),
+        ("4k3/8/8/8/8/8/8/1NN1K3 w - -", false, true),
+        // same-colored bishops can't mate without pawns or knights
+        ("2b1k3/8/8/8/8/8/8/3BK3 w - -", true, true),
+        ("2b1k2r/8/8/8/8/8/8/3BK3 w - -", true, false),
+        ("3bk3/8/8/8/8/8/8/3BK3 w - -", false, false),
+        ("4kn2/8/8/8/8/8/8/3BK3 w - -", false, false),
+        ("4k3/8/8/8/8/8/P7/3BK3 w - -", false, true),
+        ("4k3/8/8/8/8/8/8/2BBK3 w - -", false, true),
+        ("4k3/8/8/8/8/8/8/3QK3 w - -", false, true),
+    ] {
+        let board = board(epd);
+        assert_eq!(board.cannot_win(Color::White), white, "{epd}");
+        assert_eq!(board.cannot_win(Color::Black), black, "{epd}");
+
+        if board.is_insufficient_material() {
+            assert!(white && black, "{epd}");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use dychess::prelude::*;
+
+#[test]
+fn push_pop() {
+    let mut list = MoveList::new();
+    let e4 = Move::new(Square::E2, Square::E4, None);
+    let d4 = Move::new(Square::D2, Square::D4, None);
+
+    assert!(list.is_empty());
+    list.push(e4);
+    list.push(d4);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.as_slice(), [e4, d4]);
+
+    assert_eq!(list.pop(), Some(d4));
+    assert_eq!(list.pop(), Some(e4));
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn retain_and_sort() {
+    let board = Board::default();
+    let mut list = MoveList::new();
+    board.generate_into(&mut list);
+
+    list.retain(|m| board.piece_on(m.from()) == Some(Piece::Knight));
+    assert_eq!(list.len(), 4);
+
+    list.sort_by_key(|m| core::cmp::Reverse(m.to().file()));
+    let files = list.iter().map(|m| m.to().file()).collect::<Vec<_>>();
+    assert_eq!(files, [File::H, File::F, File::C, File::A]);
+}
+
+#[test]
+fn sort_is_stable() {
+    let mut list = Board::default().legal_moves(&[]).collect::<MoveList>();
+    let before = list;
+
+    list.sort_by_key(|_| 0);
+    assert_eq!(list, before);
+}
+
+#[test]
+fn max_moves() {
+    let board = Board::from_epd(false, "R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - -").unwrap();
+    let mut list = MoveList::new();
+    board.generate_into(&mut list);
+
+    assert_eq!(list.len(), 218);
+
+    // pseudo-legal moves fit too
+    assert_eq!(MovePicker::new(board.pseudo_legal_moves(&[]), MvvLva(&board)).count(), 218);
+
+    while !list.is_full() {
+        list.push(list[0]);
+    }
+
+    assert_eq!(list.len(), MoveList::CAPACITY);
+    assert_eq!(list.try_push(list[0]), Err(list[0]));
+}
@@ -0,0 +1,38 @@
+use dychess::{board::epd::EpdRecord, prelude::*};
+
+static EPD: &str = include_str!("standard.epd");
+
+#[test]
+fn descending_scores() {
+    for line in EPD.lines() {
+        let record = EpdRecord::parse(false, line).expect(line);
+        let board = record.board();
+        let mut picker = MovePicker::new(board.pseudo_legal_moves(&[]), MvvLva(board));
+
+        let mut picked = 0;
+        let mut last = i32::MAX;
+        while let Some((mov, score)) = picker.next_scored() {
+            assert!(score <= last, "{board} {mov}");
+            assert_eq!(score != 0, board.pseudo_legal_captures(&[]).any(|m| m == mov), "{board} {mov}");
+
+            last = score;
+            picked += 1;
+        }
+
+        assert_eq!(picked, board.pseudo_legal_moves(&[]).count());
+    }
+}
+
+#[test]
+fn closure_scorer() {
+    let board = Board::default();
+    let killer = Move::new(Square::G1, Square::F3, None);
+    let mut picker = MovePicker::new(board.legal_moves(&[]), |m| i32::from(m == killer));
+
+    assert_eq!(picker.len(), 20);
+    assert_eq!(picker.next(), Some(killer));
+    assert!(!picker.remaining().contains(&killer));
+
+    // moves with equal scores keep the generated order
+    assert!(picker.eq(board.legal_moves(&[]).filter(|m| *m != killer)));
+}
@@ -1,6 +1,7 @@
-use dychess::prelude::*;
+use dychess::{board::epd::EpdRecord, prelude::*};
 
 static EPD: &str = include_str!("standard.epd");
+static CHESS960_EPD: &str = include_str!("chess960.epd");
 
 #[test]
 fn test_perft() {
@@ -9,16 +10,35 @@ fn test_perft() {
     // ").unwrap(), 1);
     // panic!();
 
-    for line in EPD.lines() {
-        let (board, test) = line.split_once(" ;D").unwrap();
-        let board = Board::from_epd(false, board).expect(board);
+    perft_suite(false, EPD);
+}
 
-        for i in test.split(" ;D") {
-            let (depth, expected) = i.split_once(' ').unwrap();
-            let depth = depth.parse().unwrap();
-            let expected = expected.parse().unwrap();
+#[test]
+fn test_perft_chess960() {
+    perft_suite(true, CHESS960_EPD);
+}
 
-            assert_eq!(perft::<true>(&board, depth), expected, "{board} ; D{depth}");
+#[test]
+fn test_perft_legal() {
+    for (chess960, epd) in [(false, EPD), (true, CHESS960_EPD)] {
+        for line in epd.lines() {
+            let record = EpdRecord::parse(chess960, line).expect(line);
+            let board = record.board();
+
+            for (depth, expected) in record.perft_counts() {
+                assert_eq!(perft_legal(board, depth), expected, "{board} ; D{depth}");
+            }
+        }
+    }
+}
+
+fn perft_suite(chess960: bool, epd: &str) {
+    for line in epd.lines() {
+        let record = EpdRecord::parse(chess960, line).expect(line);
+        let board = record.board();
+
+        for (depth, expected) in record.perft_counts() {
+            assert_eq!(perft::<true>(board, depth), expected, "{board} ; D{depth}");
         }
     }
 }
@@ -48,3 +68,9 @@ fn perft<const ROOT: bool>(board: &Board, depth: usize) -> u64 {
 
     total
 }
+
+fn perft_legal(board: &Board, depth: usize) -> u64 {
+    if depth == 1 { return board.legal_moves(&[]).count() as u64 };
+
+    board.legal_moves(&[]).map(|m| perft_legal(&board.copy_make_move(m), depth - 1)).sum()
+}
@@ -0,0 +1,222 @@
+use dychess::{board::san::SanError, pgn::*, prelude::*};
+
+mod common;
+
+static ANNOTATED: &str = r#"
+% an escaped line that should be ignored
+[Event "Annotated \"quoted\" game"]
+[Site "C:\\games"]
+[Result "0-1"]
+
+{Opening comment} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4 Be7) exd4)
+3. Bc4!? Nd4?! ; rest of line comment
+4. Nxe5?? Qg5 5. Nxf7 Qxg2 6. Rf1 Qxe4+ 7. Be2 Nf3# 0-1
+"#;
+
+#[test]
+fn annotated_game() {
+    let game = PgnGame::parse(ANNOTATED).unwrap();
+
+    assert_eq!(game.tag("Event"), Some("Annotated \"quoted\" game"));
+    assert_eq!(game.tag("Site"), Some("C:\\games"));
+    assert_eq!(game.result, GameResult::BlackWins);
+    assert_eq!(game.moves.len(), 14);
+
+    assert_eq!(game.moves[0].comment_before.as_deref(), Some("Opening comment"));
+    assert_eq!(game.moves[2].nags, [1]);
+    assert_eq!(game.moves[4].nags, [5]);
+    assert_eq!(game.moves[5].nags, [6]);
+    assert_eq!(game.moves[5].comment.as_deref(), Some("rest of line comment"));
+    assert_eq!(game.moves[6].nags, [4]);
+
+    let variation = &game.moves[3].variations[0];
+    assert_eq!(variation.len(), 3);
+    assert_eq!(variation[0].mov, Move::new(Square::D7, Square::D6, None));
+    assert_eq!(variation[0].comment.as_deref(), Some("Philidor"));
+    assert_eq!(variation[1].variations[0][1].mov, Move::new(Square::F8, Square::E7, None));
+
+    let board = game.board();
+    assert!(board.is_check());
+    assert_eq!(board.legal_moves(&[]).count(), 0);
+}
+
+#[test]
+fn write_and_read_back() {
+    let game = PgnGame::parse(ANNOTATED).unwrap();
+    let written = game.to_string();
+
+    assert_eq!(written, r#"[Event "Annotated \"quoted\" game"]
+[Site "C:\\games"]
+[Result "0-1"]
+
+{Opening comment} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4 Be7)
+3... exd4) 3. Bc4 $5 Nd4 $6 {rest of line comment} 4. Nxe5 $4 Qg5 5. Nxf7 Qxg2
+6. Rf1 Qxe4+ 7. Be2 Nf3# 0-1
+"#);
+    assert_eq!(PgnGame::parse(&written), Ok(game));
+}
+
+#[test]
+fn comment_without_moves() {
+    let game = PgnGame::parse("[Event \"Empty\"]\n\n{No moves were played} *\n").unwrap();
+    assert!(game.moves.is_empty());
+    assert_eq!(game.comment.as_deref(), Some("No moves were played"));
+
+    let written = game.to_string();
+    assert_eq!(written, "[Event \"Empty\"]\n\n{No moves were played} *\n");
+    assert_eq!(PgnGame::parse(&written), Ok(game));
+}
+
+#[test]
+fn closing_braces_in_comments() {
+    let mut game = PgnGame { comment: Some("unused } brace".into()), ..PgnGame::default() };
+    game.push(Move::new(Square::E2, Square::E4, None));
+    game.moves[0].comment = Some("{nested}".into());
+    let written = game.to_string();
+
+    assert_eq!(written, "{unused  brace} 1. e4 {{nested} *\n");
+    let read = PgnGame::parse(&written).unwrap();
+    assert_eq!(read.comment, None);
+    assert_eq!(read.moves[0].comment_before.as_deref(), Some("unused  brace"));
+    assert_eq!(read.moves[0].comment.as_deref(), Some("{nested"));
+    assert_eq!(read.to_string(), written);
+}
+
+#[test]
+fn round_trip_positions() {
+    common::for_each_position(|board, _| round_trip_from(board));
+
+    for (chess960, fen) in [
+        // black to move, en passant and a late move number
+        (false, "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 57"),
+        // Chess960 castling rights that need the FEN tag
+        (true, "1rk4r/pppppppp/8/8/8/8/PPPPPPPP/1RK4R w HBhb - 3 9"),
+        // checkmate, so the game has no moves
+        (false, "R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 30"),
+    ] {
+        round_trip_from(&Board::from_fen(chess960, fen).expect(fen));
+    }
+}
+
+fn round_trip_from(start: &Board) {
+    let mut game = PgnGame::new(*start);
+    let mut board = *start;
+
+    for i in 0..40 {
+        let Some(mov) = board.legal_moves(&[]).nth(i % 7).or_else(|| board.legal_moves(&[]).next()) else { break };
+        board.make_move(mov);
+        game.push(mov);
+    }
+
+    let written = game.to_string();
+    let read = PgnGame::parse(&written).expect(&written);
+
+    assert_eq!(read.start, game.start, "{written}");
+    assert_eq!(read.moves, game.moves, "{written}");
+    assert_eq!(read.board(), board, "{written}");
+}
+
+#[test]
+fn chess960_game() {
+    let game = PgnGame::parse(r#"
+[Variant "Chess960"]
+[FEN "r3k2r/8/8/8/8/8/8/R4KR1 w GAha - 0 1"]
+
+1. O-O O-O-O *
+"#).unwrap();
+
+    assert!(game.start.is_chess960());
+    assert_eq!(game.moves[0].mov, Move::new(Square::F1, Square::G1, None));
+    assert_eq!(game.moves[1].mov, Move::new(Square::E8, Square::A8, None));
+    assert_eq!(game.board().to_fen().to_string(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+    assert_eq!(game.result, GameResult::Unknown);
+}
+
+#[test]
+fn chess960_variant_names() {
+    for variant in ["Chess960", "chess 960", "Fischerandom", "FISCHER RANDOM"] {
+        let game = PgnGame::parse(&format!("[Variant \"{variant}\"]\n\n1. e4 *")).unwrap();
+        assert!(game.start.is_chess960(), "{variant}");
+    }
+
+    for variant in ["Standard", "Crazyhouse960", "Chess9600"] {
+        let game = PgnGame::parse(&format!("[Variant \"{variant}\"]\n\n1. e4 *")).unwrap();
+        assert!(!game.start.is_chess960(), "{variant}");
+    }
+}
+
+#[test]
+fn multiple_games_and_errors() {
+    let pgn = r#"
+[Event "1"]
+
+1. e4 e5 1-0
+
+[Event "2"]
+
+1. e4 e4 2. d4 1/2-1/2
+
+[Event "3"]
+
+1. c4 c5 ) *
+
+[Event "4"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+
+[Event "5"]
+
+1. d4 {unterminated
+
+[Event "6"]
+"#;
+    let games = PgnReader::new(pgn).collect::<Vec<_>>();
+
+    assert_eq!(games.len(), 5);
+    assert_eq!(games[0].as_ref().unwrap().result, GameResult::WhiteWins);
+    assert_eq!(games[1], Err(PgnError::InvalidMove { san: "e4".into(), error: SanError::IllegalMove }));
+    assert_eq!(games[2], Err(PgnError::UnbalancedVariation));
+    assert_eq!(games[3].as_ref().unwrap().tag("Event"), Some("4"));
+    // comments can span lines, so an unterminated comment takes the rest of the input
+    assert_eq!(games[4], Err(PgnError::UnterminatedComment));
+
+    assert_eq!(PgnGame::parse("  \n"), Err(PgnError::UnexpectedEnd));
+    assert_eq!(PgnGame::parse("[Event 1]"), Err(PgnError::InvalidTag));
+    assert_eq!(PgnGame::parse("1. e4 (e5) *"), Err(PgnError::InvalidMove {
+        san: "e5".into(),
+        error: SanError::IllegalMove,
+    }));
+    assert_eq!(PgnGame::parse("1. e4 (1. d4 *"), Err(PgnError::UnbalancedVariation));
+}
+
+#[test]
+fn games_without_result() {
+    let games = PgnReader::new("[Event \"1\"]\n\n1. e4\n\n[Event \"2\"]\n\n[Event \"3\"]\n1. d4 *")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].moves.len(), 1);
+    assert_eq!(games[0].result, GameResult::Unknown);
+    assert_eq!(games[1].tags.len(), 2);
+    assert_eq!(games[1].moves.len(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn buffered_reader() {
+    let pgn = "[Event \"1\"]\n\n1. e4 e5 1-0\n\n[Event \"2\"]\n[Site \"?\"]\n\n1. d4\n{comment}\nd5 0-1\n[Event \"3\"]\n\n*\n";
+    let games = PgnBufReader::new(pgn.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[1].tags.len(), 2);
+    assert_eq!(games[1].moves[0].comment.as_deref(), Some("comment"));
+    assert_eq!(games, PgnReader::new(pgn).collect::<Result<Vec<_>, _>>().unwrap());
+}
+
+#[test]
+fn result_from_outcome() {
+    assert_eq!(GameResult::from(Outcome::Checkmate { winner: Color::White }), GameResult::WhiteWins);
+    assert_eq!(GameResult::from(Outcome::Checkmate { winner: Color::Black }), GameResult::BlackWins);
+    assert_eq!(GameResult::from(Outcome::FivefoldRepetition), GameResult::Draw);
+}
@@ -0,0 +1,46 @@
+use dychess::prelude::*;
+
+#[test]
+fn pinned_pieces() {
+    let board = Board::from_epd(false, "4k3/8/8/b7/8/8/3P4/4K2r w - -").unwrap();
+
+    // the rook pins nothing as there are no pieces between
+    assert_eq!(board.pinned(Color::White), Square::D2.into());
+    assert_eq!(board.pinners(Color::Black), Square::A5.into());
+    assert_eq!(board.pinners(Color::White), Bitboard::default());
+}
+
+#[test]
+fn queen_pins_in_both_directions() {
+    let board = Board::from_epd(false, "4k3/8/8/8/8/2q5/3B4/q1N1K3 w - -").unwrap();
+
+    assert_eq!(board.pinned(Color::White), Bitboard::from(Square::C1) | Square::D2.into());
+    assert_eq!(board.pinners(Color::Black), Bitboard::from(Square::A1) | Square::C3.into());
+}
+
+#[test]
+fn blockers_of_either_side() {
+    let board = Board::from_epd(false, "4k3/8/8/8/8/8/4n3/R3K1Nq w - -").unwrap();
+
+    // the knight on e2 is not between the king and a slider
+    assert_eq!(board.blockers_for_king(Color::White), Square::G1.into());
+
+    let board = Board::from_epd(false, "4k3/8/8/8/4B3/8/8/4RK2 b - -").unwrap();
+
+    assert_eq!(board.blockers_for_king(Color::Black), Square::E4.into());
+    assert_eq!(board.pinned(Color::Black), Bitboard::default());
+    assert_eq!(board.pinners(Color::White), Bitboard::default());
+}
+
+#[test]
+fn discovered_checks() {
+    let board = Board::from_epd(false, "4k3/8/8/8/4B3/8/8/4RK2 w - -").unwrap();
+    assert_eq!(board.discovered_check_candidates(), Square::E4.into());
+
+    // both pieces are needed to block the rook
+    let board = Board::from_epd(false, "4k3/4n3/8/8/4B3/8/8/4RK2 w - -").unwrap();
+    assert_eq!(board.discovered_check_candidates(), Bitboard::default());
+
+    let board = Board::from_epd(false, "4k3/8/8/8/4B3/8/8/4RK2 b - -").unwrap();
+    assert_eq!(board.discovered_check_candidates(), Bitboard::default());
+}
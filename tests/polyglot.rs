@@ -1,4 +1,6 @@
-use dychess::prelude::*;
+use dychess::{polyglot::*, prelude::*};
+
+mod common;
 
 #[test]
 fn polyglot_hash() {
@@ -48,3 +50,121 @@ fn zobrist_transposition() {
 
     assert_eq!(hash1, hash2);
 }
+
+fn raw(from: Square, to: Square, promotion: u16) -> u16 {
+    promotion << 12 | (from.to_u8() as u16) << 6 | to.to_u8() as u16
+}
+
+fn book_data(mut entries: Vec<PolyglotEntry>) -> Vec<u8> {
+    entries.sort_by_key(|e| e.key);
+    entries.iter().flat_map(PolyglotEntry::to_bytes).collect()
+}
+
+fn entry(board: &Board, raw_move: u16, weight: u16) -> PolyglotEntry {
+    PolyglotEntry { key: board.get_hash(), raw_move, weight, learn: 0 }
+}
+
+#[test]
+fn book_lookup() {
+    let start = Board::default();
+    let e4 = start.copy_make_move(Move::new(Square::E2, Square::E4, None));
+    let data = book_data(vec![
+        PolyglotEntry { key: 0, raw_move: 1, weight: 1, learn: 0 },
+        entry(&start, raw(Square::E2, Square::E4, 0), 10),
+        entry(&start, raw(Square::D2, Square::D4, 0), 10),
+        entry(&start, raw(Square::G1, Square::F3, 0), 0),
+        // illegal, e.g. from a hash collision
+        entry(&start, raw(Square::E2, Square::E5, 0), 100),
+        entry(&e4, raw(Square::C7, Square::C5, 0), 7),
+        PolyglotEntry { key: u64::MAX, raw_move: 1, weight: 1, learn: 0 },
+    ]);
+    let book = PolyglotBook::new(&data).unwrap();
+
+    assert_eq!(book.len(), 7);
+    assert_eq!(book.entries(&start).len(), 4);
+    assert_eq!(book.entries(&e4).map(|e| e.weight).collect::<Vec<_>>(), [7]);
+    assert_eq!(book.entries_for_hash(1).len(), 0);
+    assert_eq!(book.entries_for_hash(u64::MAX).len(), 1);
+    assert_eq!(book.moves(&start).count(), 3);
+
+    assert_eq!(book.best_move(&start), Some(Move::new(Square::E2, Square::E4, None)));
+    assert_eq!(book.best_move(&e4), Some(Move::new(Square::C7, Square::C5, None)));
+    assert_eq!(book.best_move(&e4.copy_make_move(Move::new(Square::C7, Square::C5, None))), None);
+
+    let picks = (0..20).map(|r| book.weighted_move(&start, r).unwrap()).collect::<Vec<_>>();
+    assert_eq!(picks.iter().filter(|m| m.to() == Square::E4).count(), 10);
+    assert_eq!(picks.iter().filter(|m| m.to() == Square::D4).count(), 10);
+    assert_eq!(book.weighted_move(&start, u64::MAX), Some(Move::new(Square::D2, Square::D4, None)));
+
+    let data = book_data(vec![entry(&start, raw(Square::G1, Square::F3, 0), 0)]);
+    assert_eq!(PolyglotBook::new(&data).unwrap().weighted_move(&start, 0), None);
+
+    assert_eq!(PolyglotBook::new(&data[1..]).unwrap_err(), PolyglotError::InvalidLength);
+    assert!(PolyglotBook::new(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn move_decoding() {
+    let board = Board::from_epd(false, "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq -").unwrap();
+
+    assert_eq!(decode_move(&board, raw(Square::E1, Square::H1, 0)), Some(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(decode_move(&board, raw(Square::E1, Square::A1, 0)), Some(Move::new(Square::E1, Square::C1, None)));
+    assert_eq!(decode_move(&board, raw(Square::E1, Square::F1, 0)), Some(Move::new(Square::E1, Square::F1, None)));
+    assert_eq!(decode_move(&board, raw(Square::B7, Square::A8, 1)), Some(Move::new(Square::B7, Square::A8, Some(Piece::Knight))));
+    assert_eq!(decode_move(&board, raw(Square::B7, Square::B8, 4)), Some(Move::new(Square::B7, Square::B8, Some(Piece::Queen))));
+    assert_eq!(decode_move(&board, raw(Square::B7, Square::B8, 0)), None);
+    assert_eq!(decode_move(&board, raw(Square::B7, Square::B8, 5)), None);
+    assert_eq!(decode_move(&board, raw(Square::E8, Square::H8, 0)), None);
+    assert_eq!(decode_move(&board, 0), None);
+
+    let board = Board::from_epd(true, "r3k2r/8/8/8/8/8/8/1R2K1R1 w GBha -").unwrap();
+    assert_eq!(decode_move(&board, raw(Square::E1, Square::G1, 0)), Some(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(decode_move(&board, raw(Square::E1, Square::B1, 0)), Some(Move::new(Square::E1, Square::B1, None)));
+}
+
+#[test]
+fn entry_bytes() {
+    let entry = PolyglotEntry { key: 0x0123456789abcdef, raw_move: 0x1234, weight: 0x5678, learn: 0x9abcdef0 };
+    let bytes = entry.to_bytes();
+
+    assert_eq!(bytes, [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]);
+    assert_eq!(PolyglotEntry::from_bytes(&bytes), entry);
+}
+
+#[test]
+fn move_encoding_round_trip() {
+    common::for_each_position(|board, _| encoding_round_trip(board));
+
+    for (chess960, epd) in [
+        // castling on both sides for both colors
+        (false, "r3k2r/8/8/8/8/8/8/R3K2R w KQkq -"),
+        (false, "r3k2r/8/8/8/8/8/8/R3K2R b KQkq -"),
+        // Chess960 castling where the king doesn't move or is next to the rook
+        (true, "5k2/8/8/8/8/8/8/1RK3R1 w GB -"),
+        (true, "4rkr1/8/8/8/8/8/8/1K6 b ge -"),
+        // every promotion for both colors
+        (false, "1r2k3/P7/8/8/8/8/p7/1R2K3 w - -"),
+        (false, "1r2k3/P7/8/8/8/8/p7/1R2K3 b - -"),
+    ] {
+        encoding_round_trip(&Board::from_epd(chess960, epd).expect(epd));
+    }
+}
+
+fn encoding_round_trip(board: &Board) {
+    for mov in board.legal_moves(&[]) {
+        let raw = encode_move(board, mov);
+        assert_eq!(decode_move(board, raw), Some(mov), "{board} {mov}");
+
+        // castling is always encoded as the king capturing its own rook
+        let to = Square::from_index(raw as u8 & 63);
+        let castling = board.piece_on(mov.from()) == Some(Piece::King)
+            && (board.color_on(mov.to()) == Some(board.side_to_move())
+                || (mov.from().file() as u8).abs_diff(mov.to().file() as u8) > 1);
+
+        if castling {
+            assert_eq!(board.piece_and_color_on(to), Some((Piece::Rook, board.side_to_move())), "{board} {mov}");
+        } else {
+            assert_eq!(to, mov.to(), "{board} {mov}");
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use dychess::{pgn::*, polyglot::*, prelude::*};
+
+fn raw(from: Square, to: Square) -> u16 {
+    (from.to_u8() as u16) << 6 | to.to_u8() as u16
+}
+
+#[test]
+fn merge_and_sort() {
+    let start = Board::default();
+    let e4 = Move::new(Square::E2, Square::E4, None);
+    let d4 = Move::new(Square::D2, Square::D4, None);
+    let after_e4 = start.copy_make_move(e4);
+
+    let mut builder = PolyglotBuilder::new();
+    builder.add(&after_e4, Move::new(Square::E7, Square::E5, None), 1, 0);
+    builder.add(&start, d4, 3, 1);
+    builder.add(&start, e4, 2, 0);
+    builder.add(&start, e4, 2, 5);
+
+    let entries = builder.entries();
+    assert_eq!(entries.len(), 3);
+    assert!(entries.is_sorted_by_key(|e| e.key));
+
+    let start_entries = entries.iter().filter(|e| e.key == start.get_hash()).collect::<Vec<_>>();
+    assert_eq!(start_entries, [
+        &PolyglotEntry { key: start.get_hash(), raw_move: raw(Square::E2, Square::E4), weight: 4, learn: 5 },
+        &PolyglotEntry { key: start.get_hash(), raw_move: raw(Square::D2, Square::D4), weight: 3, learn: 1 },
+    ]);
+
+    let data = builder.to_bytes();
+    let book = PolyglotBook::new(&data).unwrap();
+    assert_eq!(book.best_move(&start), Some(e4));
+    assert_eq!(book.best_move(&after_e4), Some(Move::new(Square::E7, Square::E5, None)));
+}
+
+#[test]
+fn weight_scaling() {
+    let start = Board::default();
+    let mut builder = PolyglotBuilder::new();
+
+    for _ in 0..4 {
+        builder.add(&start, Move::new(Square::E2, Square::E4, None), u16::MAX, 0);
+    }
+    builder.add(&start, Move::new(Square::D2, Square::D4, None), u16::MAX, 0);
+
+    let weights = builder.entries().iter().map(|e| e.weight).collect::<Vec<_>>();
+    assert_eq!(weights, [u16::MAX, u16::MAX / 4]);
+}
+
+#[test]
+fn filters() {
+    let games = [
+        "1. e4 e5 2. Nf3 Nc6 1-0",
+        "1. e4 e5 2. Nf3 Nf6 0-1",
+        "1. e4 c5 2. Nf3 d6 1/2-1/2",
+        "1. d4 d5 *",
+    ].map(|pgn| PgnGame::parse(pgn).unwrap());
+
+    let mut all = PolyglotBuilder::new();
+    let mut shallow = PolyglotBuilder::new().with_max_depth(2);
+    let mut common = PolyglotBuilder::new().with_min_count(2);
+    for game in &games {
+        all.add_pgn(game);
+        shallow.add_pgn(game);
+        common.add_pgn(game);
+    }
+
+    assert_eq!(all.entries().len(), 10);
+    assert_eq!(shallow.entries().len(), 5);
+    assert_eq!(common.entries().len(), 3);
+
+    let data = all.to_bytes();
+    let book = PolyglotBook::new(&data).unwrap();
+    let start = Board::default();
+
+    assert_eq!(book.moves(&start).collect::<Vec<_>>(), [
+        (Move::new(Square::E2, Square::E4, None), 3),
+        (Move::new(Square::D2, Square::D4, None), 0),
+    ]);
+
+    // every move of every game is in the book
+    for game in &games {
+        for (board, mov) in game.mainline() {
+            assert!(book.moves(&board).any(|(m, _)| m == mov), "{board} {mov}");
+        }
+    }
+
+    let after_e4_e5 = games[0].mainline().nth(2).unwrap().0;
+    assert_eq!(book.moves(&after_e4_e5).collect::<Vec<_>>(), [(Move::new(Square::G1, Square::F3, None), 2)]);
+}
+
+#[test]
+fn castling_moves() {
+    let board = Board::from_epd(false, "r3k2r/8/8/8/8/8/8/R3K2R w KQkq -").unwrap();
+    let chess960 = Board::from_epd(true, "r3k2r/8/8/8/8/8/8/1R2K1R1 w GBha -").unwrap();
+
+    let mut builder = PolyglotBuilder::new();
+    builder.add(&board, Move::new(Square::E1, Square::G1, None), 1, 0);
+    builder.add(&chess960, Move::new(Square::E1, Square::B1, None), 1, 0);
+
+    let entries = builder.entries();
+    let entry = |board: &Board| entries.iter().find(|e| e.key == board.get_hash()).unwrap().raw_move;
+    assert_eq!(entry(&board), raw(Square::E1, Square::H1));
+    assert_eq!(entry(&chess960), raw(Square::E1, Square::B1));
+
+    let data = builder.to_bytes();
+    let book = PolyglotBook::new(&data).unwrap();
+    assert_eq!(book.best_move(&board), Some(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(book.best_move(&chess960), Some(Move::new(Square::E1, Square::B1, None)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_to() {
+    let mut builder = PolyglotBuilder::new();
+    builder.add_pgn(&PgnGame::parse("1. e4 e5 2. Nf3 Nc6 1-0").unwrap());
+
+    let mut out = Vec::new();
+    builder.write_to(&mut out).unwrap();
+
+    assert_eq!(out.len(), 4 * PolyglotEntry::SIZE);
+    assert_eq!(out, builder.to_bytes());
+}
@@ -0,0 +1,106 @@
+use dychess::{board::san::SanError, prelude::*};
+
+mod common;
+
+#[test]
+fn round_trip() {
+    common::for_each_position(|board, _| round_trip_tree(board, 2));
+
+    for (chess960, epd) in [
+        // disambiguation by file, by rank and by both
+        (false, "4k3/8/1Q3Q2/8/8/1Q6/8/4K3 w - -"),
+        (false, "4k3/8/8/8/8/1N3N2/8/1N2K3 w - -"),
+        // a pinned piece doesn't need to be disambiguated from
+        (false, "4k3/4r3/8/8/8/8/2N1N3/4K3 w - -"),
+        // Chess960 castling where only the rook moves, and promotions with captures
+        (true, "1rk4r/8/8/8/8/8/8/1RK4R w KQkq -"),
+        (false, "r1n1k3/1P6/8/8/8/8/8/4K3 w - -"),
+    ] {
+        round_trip_tree(&Board::from_epd(chess960, epd).expect(epd), 2);
+    }
+}
+
+fn round_trip_tree(board: &Board, depth: usize) {
+    for m in board.legal_moves(&[]) {
+        let san = board.san(m).to_string();
+        assert_eq!(board.parse_san(&san), Ok(m), "{board} {m} {san}");
+
+        if depth > 1 {
+            round_trip_tree(&board.copy_make_move(m), depth - 1);
+        }
+    }
+}
+
+#[test]
+fn formatting() {
+    let board = Board::from_epd(false, "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -").unwrap();
+    let san = |from, to| board.san(Move::new(from, to, None)).to_string();
+
+    assert_eq!(san(Square::E1, Square::G1), "O-O");
+    assert_eq!(san(Square::E1, Square::C1), "O-O-O");
+    assert_eq!(san(Square::D5, Square::E6), "dxe6");
+    assert_eq!(san(Square::E5, Square::F7), "Nxf7");
+    assert_eq!(san(Square::F3, Square::F6), "Qxf6");
+    assert_eq!(san(Square::E2, Square::A6), "Bxa6");
+    assert_eq!(san(Square::C3, Square::B5), "Nb5");
+
+    // disambiguation by file, rank and both
+    let board = Board::from_epd(false, "k7/8/8/8/Q6Q/8/8/1K5Q w - -").unwrap();
+    assert_eq!(board.san(Move::new(Square::A4, Square::D4, None)).to_string(), "Qad4");
+    assert_eq!(board.san(Move::new(Square::H1, Square::E4, None)).to_string(), "Q1e4+");
+    assert_eq!(board.san(Move::new(Square::H4, Square::E1, None)).to_string(), "Q4e1");
+
+    let board = Board::from_epd(false, "4K1k1/8/8/8/8/Q7/8/Q1Q5 w - -").unwrap();
+    assert_eq!(board.san(Move::new(Square::A1, Square::B2, None)).to_string(), "Qa1b2");
+
+    let board = Board::from_epd(false, "8/8/8/3k4/4pP2/8/8/4K3 b - f3").unwrap();
+    assert_eq!(board.san(Move::new(Square::E4, Square::F3, None)).to_string(), "exf3");
+
+    let board = Board::from_epd(false, "7k/5Q2/6K1/8/8/8/8/8 w - -").unwrap();
+    assert_eq!(board.san(Move::new(Square::F7, Square::G7, None)).to_string(), "Qg7#");
+    assert_eq!(board.san(Move::new(Square::F7, Square::F8, None)).to_string(), "Qf8#");
+    assert_eq!(board.san(Move::new(Square::F7, Square::F6, None)).to_string(), "Qf6+");
+
+    let board = Board::from_epd(false, "4k3/4r3/8/8/8/8/2N1N3/4K3 w - -").unwrap();
+    assert_eq!(board.san(Move::new(Square::C2, Square::D4, None)).to_string(), "Nd4");
+}
+
+#[test]
+fn lenient_parsing() {
+    let board = Board::from_epd(false, "r3k3/1P6/8/8/8/8/8/R3K2R w KQq -").unwrap();
+    let promotion = Move::new(Square::B7, Square::B8, Some(Piece::Queen));
+    let capture_promotion = Move::new(Square::B7, Square::A8, Some(Piece::Knight));
+
+    assert_eq!(board.parse_san("b8=Q"), Ok(promotion));
+    assert_eq!(board.parse_san("b8Q"), Ok(promotion));
+    assert_eq!(board.parse_san("b8=Q+"), Ok(promotion));
+    assert_eq!(board.parse_san("b8(Q)"), Ok(promotion));
+    assert_eq!(board.parse_san("bxa8=N"), Ok(capture_promotion));
+    assert_eq!(board.parse_san("bxa8N"), Ok(capture_promotion));
+
+    assert_eq!(board.parse_san("O-O"), Ok(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(board.parse_san("0-0"), Ok(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(board.parse_san("0-0-0+"), Ok(Move::new(Square::E1, Square::C1, None)));
+    assert_eq!(board.parse_san("Rh1-h7"), Ok(Move::new(Square::H1, Square::H7, None)));
+    assert_eq!(board.parse_san("Rxa8"), Ok(Move::new(Square::A1, Square::A8, None)));
+    assert_eq!(board.parse_san("Ra1xa8"), Ok(Move::new(Square::A1, Square::A8, None)));
+}
+
+#[test]
+fn parsing_errors() {
+    let board = Board::from_epd(false, "r3k3/1P6/8/8/8/8/8/R3K2R w KQq -").unwrap();
+
+    assert_eq!(board.parse_san("b8"), Err(SanError::IllegalMove));
+    assert_eq!(board.parse_san("Rd1"), Ok(Move::new(Square::A1, Square::D1, None)));
+    assert_eq!(board.parse_san("Nf3"), Err(SanError::IllegalMove));
+    assert_eq!(board.parse_san("Rz1"), Err(SanError::InvalidSyntax));
+    assert_eq!(board.parse_san("b8=X"), Err(SanError::InvalidSyntax));
+    assert_eq!(board.parse_san("b8(X)"), Err(SanError::InvalidSyntax));
+    assert_eq!(board.parse_san("b8=K"), Err(SanError::InvalidSyntax));
+    assert_eq!(board.parse_san(""), Err(SanError::InvalidSyntax));
+
+    let board = Board::from_epd(false, "4k3/8/8/8/8/8/8/1N2KN2 w - -").unwrap();
+    assert_eq!(board.parse_san("Nd2"), Err(SanError::AmbiguousMove));
+    assert_eq!(board.parse_san("Nbd2"), Ok(Move::new(Square::B1, Square::D2, None)));
+    assert_eq!(board.parse_san("O-O"), Err(SanError::IllegalMove));
+}
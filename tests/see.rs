@@ -0,0 +1,106 @@
+use dychess::prelude::*;
+
+mod common;
+
+fn see(fen: &str, from: Square, to: Square, promotion: Option<Piece>) -> i32 {
+    let board = Board::from_epd(false, fen).unwrap();
+    let mov = Move::new(from, to, promotion);
+    let see = board.see(mov);
+
+    assert!(board.see_ge(mov, see));
+    assert!(!board.see_ge(mov, see + 1));
+    see
+}
+
+#[test]
+fn captures() {
+    assert_eq!(see("4k3/8/8/3p4/8/8/8/3RK3 w - -", Square::D1, Square::D5, None), 100);
+    assert_eq!(see("4k3/8/4p3/3n4/2P5/8/8/4K3 w - -", Square::C4, Square::D5, None), 200);
+    assert_eq!(see("4k3/8/2p5/3r4/8/8/3R4/4K3 w - -", Square::D2, Square::D5, None), 0);
+    assert_eq!(see("4k3/8/4p3/3p4/8/8/8/3QK3 w - -", Square::D1, Square::D5, None), -800);
+}
+
+#[test]
+fn x_rays() {
+    assert_eq!(see("4k3/8/2p5/3r4/8/8/3R4/3QK3 w - -", Square::D2, Square::D5, None), 100);
+    assert_eq!(see("3qk3/3r4/8/3p4/8/8/3R4/3QK3 w - -", Square::D2, Square::D5, None), -400);
+    assert_eq!(see("4k3/8/4p3/3p4/2B5/8/8/4K3 w - -", Square::C4, Square::D5, None), -200);
+    assert_eq!(see("4k3/8/4p3/3p4/2B5/1Q6/8/4K3 w - -", Square::C4, Square::D5, None), -100);
+}
+
+#[test]
+fn king_captures() {
+    assert_eq!(see("8/8/8/8/8/2k5/3p4/3RK3 w - -", Square::D1, Square::D2, None), 100);
+    assert_eq!(see("8/8/8/8/8/2k5/3p4/3RK3 w - -", Square::E1, Square::D2, None), 100);
+}
+
+#[test]
+fn en_passant() {
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6", Square::E5, Square::D6, None), 100);
+    assert_eq!(see("4k3/2p5/8/3pP3/8/8/8/4K3 w - d6", Square::E5, Square::D6, None), 0);
+}
+
+#[test]
+fn promotions() {
+    assert_eq!(see("4k3/1P6/8/8/8/8/8/4K3 w - -", Square::B7, Square::B8, Some(Piece::Queen)), 800);
+    assert_eq!(see("r3k3/1P6/8/8/8/8/8/4K3 w - -", Square::B7, Square::B8, Some(Piece::Queen)), -100);
+    assert_eq!(see("r3k3/1P6/8/8/8/8/8/4K3 w - -", Square::B7, Square::A8, Some(Piece::Queen)), 1300);
+    assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 b - -", Square::B8, Square::B1, None), 0);
+
+    // the rook doesn't recapture, since the pawn would recapture with a promotion
+    assert_eq!(see("1n1rk3/P7/8/8/8/8/8/1Q2K3 w - -", Square::B1, Square::B8, None), 300);
+    assert_eq!(see("4k3/8/8/8/8/8/4p3/3QK3 b - -", Square::E8, Square::D8, None), 0);
+    assert_eq!(see("4k3/8/8/8/8/8/4p3/2R1K3 w - -", Square::C1, Square::D1, None), -400);
+}
+
+#[test]
+fn quiet_moves() {
+    assert_eq!(see("4k3/8/2p5/8/8/8/8/3RK3 w - -", Square::D1, Square::D5, None), -500);
+    assert_eq!(see("4k3/8/8/8/8/8/8/3RK3 w - -", Square::D1, Square::D5, None), 0);
+    assert_eq!(see("4k3/8/8/8/8/8/8/R3K2R w KQ -", Square::E1, Square::G1, None), 0);
+
+    // white recaptures on f5 to lose less, and then black recaptures again
+    assert_eq!(see("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -", Square::F3, Square::F5, None), -900);
+}
+
+#[test]
+fn early_exit() {
+    // a long exchange on d5 where the threshold is settled after the first captures, while `see`
+    // follows it to the end
+    let board = Board::from_epd(false, "3qk3/3r4/2n1p3/3p4/2P1N3/1B6/3R4/3QK3 w - -").unwrap();
+    let mov = Move::new(Square::C4, Square::D5, None);
+    let see = board.see(mov);
+
+    assert!(board.see_ge(mov, -10_000));
+    assert!(!board.see_ge(mov, 10_000));
+    assert!(board.see_ge(mov, see));
+    assert!(!board.see_ge(mov, see + 1));
+}
+
+#[test]
+fn threshold_matches_see() {
+    common::for_each_position(|board, _| check_thresholds(board));
+
+    // promotions on both sides, batteries behind pinned pieces and defended kings
+    for epd in [
+        "1n1rk3/P1P5/8/8/8/8/p1p5/1Q1RK3 w - -",
+        "1n1rk3/P1P5/8/8/8/8/p1p5/1Q1RK3 b - -",
+        "3qk3/3r4/3r4/3p4/4P3/2B5/3R4/3QK3 w - -",
+        "4k3/4r3/8/1b2N3/8/8/4Q3/4K3 w - -",
+        "8/8/3k4/3p4/2P1P3/8/8/4K3 w - -",
+    ] {
+        check_thresholds(&Board::from_epd(false, epd).expect(epd));
+    }
+}
+
+fn check_thresholds(board: &Board) {
+    for mov in board.pseudo_legal_moves(&[]) {
+        let see = board.see(mov);
+
+        for threshold in [-1000, -500, -301, -300, -100, -1, 0, 1, 100, 299, 300, 500, 900, 1300] {
+            assert_eq!(board.see_ge(mov, threshold), see >= threshold, "{board} {mov} {threshold}");
+        }
+        assert!(board.see_ge(mov, see), "{board} {mov}");
+        assert!(!board.see_ge(mov, see + 1), "{board} {mov}");
+    }
+}
@@ -0,0 +1,57 @@
+use dychess::prelude::*;
+
+mod common;
+
+/// Positions with quiet checks and captures that are easy to put in the wrong stage.
+static EDGE_CASES: &[(bool, &str)] = &[
+    // castling gives check
+    (false, "5k2/8/8/8/8/8/8/4K2R w K -"),
+    (true, "3k4/8/8/8/8/8/8/1R2K3 w Q -"),
+    // pawn pushes discover checks
+    (false, "7k/8/8/8/8/8/1P6/B5K1 w - -"),
+    // promotions and underpromotions give check
+    (false, "8/1P1k4/8/8/8/8/8/4K3 w - -"),
+    // en passant discovers check
+    (false, "8/8/8/k1pP3R/8/8/8/4K3 w - c6"),
+];
+
+#[test]
+fn captures_and_quiets() {
+    for_each_position(|board| {
+        let all = board.pseudo_legal_moves(&[]).count();
+        let captures = board.pseudo_legal_captures(&[]).count();
+        let quiets = board.pseudo_legal_quiets(&[]).count();
+
+        assert_eq!(captures + quiets, all, "{board}");
+        assert!(board.pseudo_legal_quiets(&[]).all(|m| (board.their_pieces() & m.to().into()).is_empty()), "{board}");
+
+        let legal = board.legal_moves(&[]).count();
+        assert_eq!(board.legal_captures(&[]).count() + board.legal_quiets(&[]).count(), legal, "{board}");
+    });
+}
+
+#[test]
+fn quiet_checks() {
+    for_each_position(|board| {
+        let expected = board.legal_quiets(&[]).filter(|m| board.copy_make_move(*m).is_check());
+
+        assert!(expected.eq(board.legal_quiet_checks(&[])), "{board}");
+    });
+}
+
+/// Call `f` with the perft suite and edge case positions, and every position after a legal move
+/// from them.
+fn for_each_position(mut f: impl FnMut(&Board)) {
+    let mut with_children = |board: &Board| {
+        f(board);
+
+        for m in board.legal_moves(&[]) {
+            f(&board.copy_make_move(m));
+        }
+    };
+
+    common::for_each_position(|board, _| with_children(board));
+    for &(chess960, epd) in EDGE_CASES {
+        with_children(&Board::from_epd(chess960, epd).expect(epd));
+    }
+}
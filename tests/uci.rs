@@ -0,0 +1,59 @@
+use dychess::{board::uci::UciMoveError, prelude::*};
+
+mod common;
+
+#[test]
+fn round_trip() {
+    common::for_each_position(|board, _| round_trip_moves(board));
+
+    for (chess960, epd) in [
+        // castling where the king doesn't move, or swaps with the rook
+        (true, "1rk4r/8/8/8/8/8/8/1RK4R w KQkq -"),
+        (true, "5kr1/8/8/8/8/8/8/4K3 b k -"),
+        // en passant and every promotion, with and without captures
+        (false, "r3k3/1P6/8/3pP3/8/8/6p1/4K2R w K d6"),
+        (false, "r3k3/1P6/8/8/3pP3/8/6p1/4K2R b q e3"),
+    ] {
+        round_trip_moves(&Board::from_epd(chess960, epd).expect(epd));
+    }
+}
+
+fn round_trip_moves(board: &Board) {
+    for m in board.legal_moves(&[]) {
+        assert_eq!(Move::from_uci(board, &m.to_string()), Ok(m), "{board} {m}");
+    }
+}
+
+#[test]
+fn castling_conventions() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq -";
+
+    let board = Board::from_epd(false, fen).unwrap();
+    assert_eq!(Move::from_uci(&board, "e1h1"), Ok(Move::new(Square::E1, Square::G1, None)));
+    assert_eq!(Move::from_uci(&board, "e1a1"), Ok(Move::new(Square::E1, Square::C1, None)));
+
+    let board = Board::from_epd(true, fen).unwrap();
+    assert_eq!(Move::from_uci(&board, "e1g1"), Ok(Move::new(Square::E1, Square::H1, None)));
+    assert_eq!(Move::from_uci(&board, "e1c1"), Ok(Move::new(Square::E1, Square::A1, None)));
+    assert_eq!(Move::from_uci(&board, "e1f1"), Ok(Move::new(Square::E1, Square::F1, None)));
+
+    // the king and rook are next to their castling squares, so only the Chess960 notation works
+    let board = Board::from_epd(true, "1r4kr/8/8/8/8/8/8/1R4KR b KQkq - 0 1").unwrap();
+    assert_eq!(Move::from_uci(&board, "g8h8"), Ok(Move::new(Square::G8, Square::H8, None)));
+}
+
+#[test]
+fn errors() {
+    let board = Board::from_epd(false, "4k3/P7/8/8/8/8/8/4K3 w - -").unwrap();
+
+    assert_eq!(Move::from_uci(&board, "a7a8q"), Ok(Move::new(Square::A7, Square::A8, Some(Piece::Queen))));
+    assert_eq!(Move::from_uci(&board, "a7a8"), Err(UciMoveError::IllegalMove));
+    assert_eq!(Move::from_uci(&board, "a7a8k"), Err(UciMoveError::InvalidPromotion('k')));
+    assert_eq!(Move::from_uci(&board, "e1e2q"), Err(UciMoveError::IllegalMove));
+    assert_eq!(Move::from_uci(&board, "e1e1"), Err(UciMoveError::IllegalMove));
+    assert_eq!(Move::from_uci(&board, "e1i2"), Err(UciMoveError::InvalidSquare));
+    assert_eq!(Move::from_uci(&board, "e1e"), Err(UciMoveError::InvalidLength));
+    assert_eq!(Move::from_uci(&board, "e1e2qq"), Err(UciMoveError::InvalidLength));
+    assert_eq!(Move::from_uci(&board, "0000"), Err(UciMoveError::InvalidSquare));
+    assert_eq!(Move::from_uci(&board, ""), Err(UciMoveError::InvalidLength));
+}
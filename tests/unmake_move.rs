@@ -0,0 +1,58 @@
+use dychess::prelude::*;
+
+mod common;
+
+#[test]
+fn unmake_move() {
+    common::for_each_record(|record, _| {
+        let mut board = *record.board();
+
+        let nodes = make_unmake(&mut board, 3);
+        if let Some(expected) = record.perft(3) {
+            assert_eq!(nodes, expected, "{board}");
+        }
+        assert_eq!(&board, record.board());
+    });
+}
+
+#[test]
+fn special_moves() {
+    for (chess960, epd) in [
+        // en passant, including one that would expose the king
+        (false, "8/8/8/KPp4r/8/8/8/4k3 w - c6"),
+        (false, "4k3/8/8/8/3pP3/8/8/4K3 b - e3"),
+        // promotions with and without captures, taking away castling rights
+        (false, "r3k2r/1P4P1/8/8/8/8/1p4p1/R3K2R w KQkq -"),
+        (false, "r3k2r/1P4P1/8/8/8/8/1p4p1/R3K2R b KQkq -"),
+        // castling where the king or rook doesn't move
+        (true, "1rk4r/8/8/8/8/8/8/1RK4R w KQkq -"),
+        (true, "r1k4r/8/8/8/8/8/8/R1K4R w HAha -"),
+        (true, "2kr4/8/8/8/8/8/8/2KR4 w D -"),
+    ] {
+        let start = Board::from_epd(chess960, epd).expect(epd);
+        let mut board = start;
+
+        make_unmake(&mut board, 3);
+        assert_eq!(board, start);
+    }
+}
+
+fn make_unmake(board: &mut Board, depth: usize) -> u64 {
+    if depth == 0 { return 1 };
+
+    let before = *board;
+    let moves = board.pseudo_legal_moves(&[]).collect::<MoveList>();
+    let mut total = 0;
+    for &m in &moves {
+        let restorer = board.make_move(m);
+
+        if !board.is_illegal() {
+            total += make_unmake(board, depth - 1);
+        }
+
+        board.unmake_move(m, restorer);
+        assert_eq!(*board, before, "{m}");
+    }
+
+    total
+}
@@ -0,0 +1,85 @@
+use core::num::NonZeroU16;
+
+use dychess::prelude::*;
+
+mod common;
+
+#[test]
+fn all_moves() {
+    common::for_each_position(|board, _| check_all_moves(board));
+}
+
+#[test]
+fn edge_cases() {
+    for (chess960, epd) in [
+        // en passant that exposes the king, and en passant out of check
+        (false, "8/8/8/KPp4r/8/8/8/4k3 w - c6"),
+        (false, "8/8/8/2pP4/1K6/8/8/4k3 w - c6"),
+        // pinned pieces and double check
+        (false, "4k3/4r3/8/b7/8/2N5/4R3/4K3 w - -"),
+        (false, "4k3/8/8/8/1b6/8/4r3/R3K2R w KQ -"),
+        // castling through attacked squares, and Chess960 castling onto attacked squares
+        (false, "r3k2r/8/8/8/8/8/8/R3K2R w KQkq -"),
+        (false, "4k3/8/8/8/8/8/5r2/R3K2R w KQ -"),
+        (true, "2r1k3/8/8/8/8/8/8/RK6 w A -"),
+        (true, "1r2k1r1/8/8/8/8/8/8/1R3KR1 w GB -"),
+        // promotions, with the pawn pinned along the diagonal
+        (false, "r1n1k3/1P6/8/8/8/8/8/4K3 w - -"),
+        (false, "1n1bk3/2P5/8/K7/8/8/8/8 w - -"),
+    ] {
+        check_all_moves(&Board::from_epd(chess960, epd).expect(epd));
+    }
+}
+
+fn check_all_moves(board: &Board) {
+    let pseudo_legal = board.pseudo_legal_moves(&[]).collect::<MoveList>();
+    let legal = board.legal_moves(&[]).collect::<MoveList>();
+
+    for mov in every_move() {
+        assert_eq!(board.is_pseudo_legal(mov), pseudo_legal.contains(&mov), "{board} {mov}");
+        assert_eq!(board.is_legal(mov), legal.contains(&mov), "{board} {mov}");
+    }
+}
+
+#[test]
+fn priority_moves_are_validated() {
+    let board = Board::default();
+    let priority = [
+        Move::new(Square::E7, Square::E5, None),
+        Move::new(Square::E2, Square::E5, None),
+        Move::new(Square::E2, Square::E4, Some(Piece::Knight)),
+        Move::new(Square::G1, Square::F3, None),
+    ];
+
+    let mut moves = board.pseudo_legal_moves(&priority);
+    assert_eq!(moves.next(), Some(Move::new(Square::G1, Square::F3, None)));
+    assert_eq!(moves.count(), 19);
+}
+
+#[test]
+fn priority_moves_are_generated_once() {
+    let board = Board::from_epd(false, "2n1k3/1P6/8/8/8/8/8/4K3 w - -").unwrap();
+    let priority = [
+        Move::new(Square::B7, Square::B8, Some(Piece::Queen)),
+        Move::new(Square::B7, Square::C8, Some(Piece::Knight)),
+        Move::new(Square::B7, Square::B8, Some(Piece::Queen)),
+        Move::new(Square::E1, Square::D1, None),
+        Move::new(Square::B7, Square::C8, Some(Piece::Knight)),
+        Move::new(Square::E1, Square::D1, None),
+    ];
+
+    let moves = board.pseudo_legal_moves(&priority).collect::<MoveList>();
+    let mut expected = board.pseudo_legal_moves(&[]).collect::<MoveList>();
+
+    assert_eq!(moves[..3], [priority[0], priority[1], priority[3]]);
+
+    let mut sorted = moves;
+    sorted.sort_by_key(|m| format!("{m}"));
+    expected.sort_by_key(|m| format!("{m}"));
+    assert_eq!(sorted, expected);
+}
+
+fn every_move() -> impl Iterator<Item = Move> {
+    // SAFETY: the promotion field is always in the range of `0..6`
+    (1..6 << 12).filter_map(NonZeroU16::new).map(|val| unsafe { Move::from_value(val) })
+}